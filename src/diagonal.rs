@@ -0,0 +1,93 @@
+/*
+   The six cells across each corner, named after the two edges either side of the corner.
+   Diagonal i passes through corner i (see HexVertex) and is the sum of Edge i and Edge i + 1.
+*/
+
+use crate::{Edge, HexCoord};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Diagonal {
+    QQR,
+    QRR,
+    RRS,
+    RSS,
+    SQS,
+    QSQ,
+}
+
+impl Diagonal {
+    pub const ALL: [Diagonal; 6] = [
+        Diagonal::QQR,
+        Diagonal::QRR,
+        Diagonal::RRS,
+        Diagonal::RSS,
+        Diagonal::SQS,
+        Diagonal::QSQ,
+    ];
+
+    pub fn offset_flat(self) -> HexCoord {
+        match self {
+            Diagonal::QQR => HexCoord::new(1, -2, 1),
+            Diagonal::QRR => HexCoord::new(2, -1, -1),
+            Diagonal::RRS => HexCoord::new(1, 1, -2),
+            Diagonal::RSS => HexCoord::new(-1, 2, -1),
+            Diagonal::SQS => HexCoord::new(-2, 1, 1),
+            Diagonal::QSQ => HexCoord::new(-1, -1, 2),
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            Diagonal::QQR => 0,
+            Diagonal::QRR => 1,
+            Diagonal::RRS => 2,
+            Diagonal::RSS => 3,
+            Diagonal::SQS => 4,
+            Diagonal::QSQ => 5,
+        }
+    }
+
+    pub fn from_index(i: usize) -> Self {
+        match Self::ALL.get(i) {
+            Some(d) => *d,
+            None => panic!("Index '{i}' out of range"),
+        }
+    }
+
+    // The two edges either side of the diagonal
+    #[inline]
+    pub fn edges(self) -> [Edge; 2] {
+        let e = Edge::from_index(self.index());
+        [e, e.rotate_cw(1)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HexCoordinate;
+
+    #[test]
+    fn offset_is_sum_of_edges() {
+        for d in Diagonal::ALL {
+            let [a, b] = d.edges();
+            assert_eq!(d.offset_flat(), a.offset_flat() + b.offset_flat());
+            assert_eq!(Diagonal::from_index(d.index()), d);
+        }
+    }
+
+    #[test]
+    fn diagonal_neighbours_are_two_away_and_share_two_neighbours() {
+        let c = HexCoord::from_axial(3, -1);
+        for d in Diagonal::ALL {
+            let n = c.diagonal_neighbour(d);
+            assert_eq!(c.dist(n), 2);
+            let shared = Edge::ALL
+                .iter()
+                .filter(|e| n.dist(c.neighbour(**e)) == 1)
+                .count();
+            assert_eq!(shared, 2);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// Compass names for the six edges. Flat hexagons have faces pointing N and S, pointy hexagons
+// have faces pointing E and W, so each orientation gets its own set of names.
+
+use crate::{Edge, HexOrientation};
+
+#[cfg(not(feature = "bevy"))]
+type Vec2 = glam::Vec2;
+#[cfg(feature = "bevy")]
+type Vec2 = bevy::math::Vec2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlatDirection {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointyDirection {
+    NE,
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+}
+
+impl FlatDirection {
+    pub const ALL: [FlatDirection; 6] = [
+        FlatDirection::N,
+        FlatDirection::NE,
+        FlatDirection::SE,
+        FlatDirection::S,
+        FlatDirection::SW,
+        FlatDirection::NW,
+    ];
+
+    #[inline]
+    pub fn edge(self) -> Edge {
+        self.into()
+    }
+
+    #[inline]
+    pub fn vec(self) -> Vec2 {
+        self.edge().vec(HexOrientation::Flat)
+    }
+}
+
+impl PointyDirection {
+    pub const ALL: [PointyDirection; 6] = [
+        PointyDirection::NE,
+        PointyDirection::E,
+        PointyDirection::SE,
+        PointyDirection::SW,
+        PointyDirection::W,
+        PointyDirection::NW,
+    ];
+
+    #[inline]
+    pub fn edge(self) -> Edge {
+        self.into()
+    }
+
+    #[inline]
+    pub fn vec(self) -> Vec2 {
+        self.edge().vec(HexOrientation::Pointy)
+    }
+}
+
+impl From<FlatDirection> for Edge {
+    fn from(value: FlatDirection) -> Self {
+        match value {
+            FlatDirection::N => Edge::Q,
+            FlatDirection::NE => Edge::QR,
+            FlatDirection::SE => Edge::R,
+            FlatDirection::S => Edge::RS,
+            FlatDirection::SW => Edge::S,
+            FlatDirection::NW => Edge::QS,
+        }
+    }
+}
+
+impl From<Edge> for FlatDirection {
+    fn from(value: Edge) -> Self {
+        match value {
+            Edge::Q => FlatDirection::N,
+            Edge::QR => FlatDirection::NE,
+            Edge::R => FlatDirection::SE,
+            Edge::RS => FlatDirection::S,
+            Edge::S => FlatDirection::SW,
+            Edge::QS => FlatDirection::NW,
+        }
+    }
+}
+
+impl From<PointyDirection> for Edge {
+    fn from(value: PointyDirection) -> Self {
+        match value {
+            PointyDirection::NE => Edge::QR,
+            PointyDirection::E => Edge::R,
+            PointyDirection::SE => Edge::RS,
+            PointyDirection::SW => Edge::S,
+            PointyDirection::W => Edge::QS,
+            PointyDirection::NW => Edge::Q,
+        }
+    }
+}
+
+impl From<Edge> for PointyDirection {
+    fn from(value: Edge) -> Self {
+        match value {
+            Edge::QR => PointyDirection::NE,
+            Edge::R => PointyDirection::E,
+            Edge::RS => PointyDirection::SE,
+            Edge::S => PointyDirection::SW,
+            Edge::QS => PointyDirection::W,
+            Edge::Q => PointyDirection::NW,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HexCoord, HexWorld, HexWorldShape};
+    use std::f32::consts::PI;
+    use test_case::test_case;
+
+    fn compass_angle(degrees: f32) -> Vec2 {
+        Vec2::from_angle(degrees * PI / 180.0)
+    }
+
+    #[test_case(FlatDirection::N, 90.0)]
+    #[test_case(FlatDirection::NE, 30.0)]
+    #[test_case(FlatDirection::SE, -30.0)]
+    #[test_case(FlatDirection::S, -90.0)]
+    #[test_case(FlatDirection::SW, -150.0)]
+    #[test_case(FlatDirection::NW, 150.0)]
+    fn flat_vec_points_at_compass(direction: FlatDirection, degrees: f32) {
+        assert!(direction.vec().distance(compass_angle(degrees)) < 1e-5);
+    }
+
+    #[test_case(PointyDirection::NE, 60.0)]
+    #[test_case(PointyDirection::E, 0.0)]
+    #[test_case(PointyDirection::SE, -60.0)]
+    #[test_case(PointyDirection::SW, -120.0)]
+    #[test_case(PointyDirection::W, 180.0)]
+    #[test_case(PointyDirection::NW, 120.0)]
+    fn pointy_vec_points_at_compass(direction: PointyDirection, degrees: f32) {
+        assert!(direction.vec().distance(compass_angle(degrees)) < 1e-5);
+    }
+
+    #[test]
+    fn edge_round_trip() {
+        for e in Edge::ALL {
+            assert_eq!(FlatDirection::from(e).edge(), e);
+            assert_eq!(PointyDirection::from(e).edge(), e);
+        }
+        for (f, p) in FlatDirection::ALL.iter().zip(PointyDirection::ALL) {
+            assert_eq!(FlatDirection::from(f.edge()), *f);
+            assert_eq!(PointyDirection::from(p.edge()), p);
+        }
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn vec_matches_world_step(orientation: HexOrientation) {
+        // Stepping along the edge must move the cell center the way the compass says
+        let world = HexWorld::new(HexWorldShape::Hexagon(3, orientation), 1.0);
+        let origin = HexCoord::from_axial(0, 0);
+        for e in Edge::ALL {
+            let step = (world.coord_to_world(origin + e.offset_flat()) - world.coord_to_world(origin)).normalize();
+            assert!(step.distance(e.vec(orientation)) < 1e-5);
+        }
+    }
+}
@@ -8,9 +8,17 @@
         rs
 */
 
-use crate::HexCoord;
+use std::f32::consts::PI;
 
-#[derive(Copy, Clone)]
+use crate::{HexCoord, HexCoordinate, HexOrientation};
+
+#[cfg(not(feature = "bevy"))]
+type Vec2 = glam::Vec2;
+#[cfg(feature = "bevy")]
+type Vec2 = bevy::math::Vec2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edge {
     Q,
     QR,
@@ -21,6 +29,8 @@ pub enum Edge {
 }
 
 impl Edge {
+    pub const ALL: [Edge; 6] = [Edge::Q, Edge::QR, Edge::R, Edge::RS, Edge::S, Edge::QS];
+
     pub fn offset_flat(self) -> HexCoord {
         match self {
             Edge::Q => HexCoord::new(0, -1, 1),
@@ -43,15 +53,152 @@ impl Edge {
         }
     }
 
+    #[inline]
+    pub fn opposite(self) -> Self {
+        self.rotate_cw(3)
+    }
+
+    #[inline]
+    pub fn rotate_cw(self, steps: usize) -> Self {
+        Self::from_index((self.index() + steps) % 6)
+    }
+
+    #[inline]
+    pub fn rotate_ccw(self, steps: usize) -> Self {
+        Self::from_index((self.index() + 6 - steps % 6) % 6)
+    }
+
     pub fn from_index(i: usize) -> Self {
-        match i {
-            0 => Edge::Q,
-            1 => Edge::QR,
-            2 => Edge::R,
-            3 => Edge::RS,
-            4 => Edge::S,
-            5 => Edge::QS,
-            _ => panic!("Index '{i}' out of range"),
+        match Self::try_from_index(i) {
+            Some(e) => e,
+            None => panic!("Index '{i}' out of range"),
+        }
+    }
+
+    #[inline]
+    pub fn try_from_index(i: usize) -> Option<Self> {
+        Self::ALL.get(i).copied()
+    }
+
+    // The edge leading to an adjacent cell offset, None if the offset isn't a single step
+    pub fn from_offset(offset: HexCoord) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.offset_flat() == offset)
+    }
+
+    // The edge pointing closest to the direction from a to b, ties go to the lowest index.
+    // None if a and b are the same cell.
+    pub fn direction_to(a: HexCoord, b: HexCoord) -> Option<Self> {
+        if a == b {
+            return None;
+        }
+
+        // Cube space is skewed so compare directions in world space, any orientation will do
+        let to_world = |c: HexCoord| {
+            let (q, r, _) = c.qrs_f32();
+            let o = HexOrientation::Flat;
+            Vec2::new(o.f0() * q + o.f1() * r, o.f2() * q + o.f3() * r)
+        };
+        let direction = to_world(b - a).normalize();
+
+        let mut best = (f32::MIN, Edge::Q);
+        for e in Self::ALL {
+            let d = to_world(e.offset_flat()).normalize().dot(direction);
+            if d > best.0 + 1e-6 {
+                best = (d, e);
+            }
+        }
+        Some(best.1)
+    }
+
+    // World space direction of the edge's face, matching HexOrientation::face_vec
+    #[inline]
+    pub fn vec(self, orientation: HexOrientation) -> Vec2 {
+        orientation.face_vec(self.index())
+    }
+
+    // World space angle of the edge's face in radians
+    #[inline]
+    pub fn angle(self, orientation: HexOrientation) -> f32 {
+        self.vec(orientation).to_angle()
+    }
+
+    // The edge whose face points closest to the given world space angle in radians
+    pub fn from_angle(angle: f32, orientation: HexOrientation) -> Self {
+        let start = Edge::Q.angle(orientation);
+        // Edges run clockwise so the index increases as the angle decreases
+        let steps = ((start - angle) / (PI / 3.0)).round() as i32;
+        Self::from_index(usize::try_from(steps.rem_euclid(6)).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn all_is_in_index_order() {
+        for (i, e) in Edge::ALL.iter().enumerate() {
+            assert_eq!(e.index(), i);
+            assert_eq!(Edge::from_index(i), *e);
         }
     }
+
+    #[test]
+    fn try_from_index_out_of_range_is_none() {
+        assert_eq!(Edge::try_from_index(5), Some(Edge::QS));
+        assert_eq!(Edge::try_from_index(6), None);
+    }
+
+    #[test_case(Edge::Q, Edge::RS)]
+    #[test_case(Edge::QR, Edge::S)]
+    #[test_case(Edge::R, Edge::QS)]
+    fn opposite(edge: Edge, expected: Edge) {
+        assert_eq!(edge.opposite(), expected);
+        assert_eq!(expected.opposite(), edge);
+        assert_eq!(edge.offset_flat() + expected.offset_flat(), HexCoord::from_axial(0, 0));
+    }
+
+    #[test_case(Edge::Q, 1, Edge::QR)]
+    #[test_case(Edge::QS, 2, Edge::QR)]
+    #[test_case(Edge::R, 9, Edge::QS)]
+    fn rotate_cw(edge: Edge, steps: usize, expected: Edge) {
+        assert_eq!(edge.rotate_cw(steps), expected);
+        assert_eq!(expected.rotate_ccw(steps), edge);
+    }
+
+    #[test]
+    fn from_offset() {
+        for e in Edge::ALL {
+            assert_eq!(Edge::from_offset(e.offset_flat()), Some(e));
+        }
+        assert_eq!(Edge::from_offset(HexCoord::from_axial(2, 0)), None);
+        assert_eq!(Edge::from_offset(HexCoord::from_axial(0, 0)), None);
+    }
+
+    #[test_case(HexCoord::from_axial(0, 0), HexCoord::from_axial(5, 0), Some(Edge::R))]
+    #[test_case(HexCoord::from_axial(0, 0), HexCoord::from_axial(0, -3), Some(Edge::Q))]
+    #[test_case(HexCoord::from_axial(2, 2), HexCoord::from_axial(-1, 3), Some(Edge::QS))]
+    #[test_case(HexCoord::from_axial(0, 0), HexCoord::from_axial(2, -1), Some(Edge::QR))]
+    #[test_case(HexCoord::from_axial(0, 0), HexCoord::from_axial(1, 1), Some(Edge::R))]
+    #[test_case(HexCoord::from_axial(1, 1), HexCoord::from_axial(1, 1), None)]
+    fn direction_to(a: HexCoord, b: HexCoord, expected: Option<Edge>) {
+        assert_eq!(Edge::direction_to(a, b), expected);
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn angle_round_trip(orientation: HexOrientation) {
+        for e in Edge::ALL {
+            assert_eq!(Edge::from_angle(e.angle(orientation), orientation), e);
+            assert_eq!(Edge::from_angle(e.angle(orientation) + 0.4, orientation), e);
+            assert_eq!(Edge::from_angle(e.angle(orientation) - 0.4 + 4.0 * PI, orientation), e);
+        }
+    }
+
+    #[test]
+    fn flat_q_points_north() {
+        assert_eq!(Edge::from_angle(PI / 2.0, HexOrientation::Flat), Edge::Q);
+        assert_eq!(Edge::from_angle(0.0, HexOrientation::Pointy), Edge::R);
+    }
 }
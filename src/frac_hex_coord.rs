@@ -0,0 +1,248 @@
+use crate::{hex_coord::cube_round, hex_trait::HexCoordinate, Edge, HexCoord};
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Sub},
+};
+
+// A position in cube space that doesn't have to sit on a cell center, used for interpolation
+// and sub-cell movement. Round back to a HexCoord with `round`.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct FracHexCoord([f32; 3]);
+
+impl FracHexCoord {
+    pub fn new(q: f32, r: f32, s: f32) -> Self {
+        assert!((q + r + s).abs() < 1e-3, "QRS must add up to 0");
+        Self([q, r, s])
+    }
+
+    #[inline]
+    pub(crate) const fn new_unchecked(q: f32, r: f32, s: f32) -> Self {
+        Self([q, r, s])
+    }
+
+    #[inline]
+    pub fn from_axial(q: f32, r: f32) -> Self {
+        Self([q, r, -q - r])
+    }
+
+    pub fn lerp(self, rhs: Self, t: f32) -> Self {
+        Self([
+            self.0[0] + (rhs.0[0] - self.0[0]) * t,
+            self.0[1] + (rhs.0[1] - self.0[1]) * t,
+            self.0[2] + (rhs.0[2] - self.0[2]) * t,
+        ])
+    }
+
+    #[inline]
+    pub fn round(self) -> HexCoord {
+        cube_round(self.0[0], self.0[1], self.0[2])
+    }
+}
+
+impl HexCoordinate<f32> for FracHexCoord {
+    fn dist(&self, rhs: Self) -> f32 {
+        let d = *self - rhs;
+        (d.0[0].abs() + d.0[1].abs() + d.0[2].abs()) / 2.0
+    }
+
+    fn qrs(&self) -> (f32, f32, f32) {
+        (self.0[0], self.0[1], self.0[2])
+    }
+
+    fn qrs_f32(&self) -> (f32, f32, f32) {
+        self.qrs()
+    }
+
+    fn neighbour(self, edge: Edge) -> Self {
+        self + edge.offset_flat().into()
+    }
+
+    fn ring(self, radius: usize) -> Vec<Self> {
+        self.round()
+            .ring(radius)
+            .into_iter()
+            .map(|c| self + (c - self.round()).into())
+            .collect()
+    }
+
+    #[inline]
+    fn q(&self) -> f32 {
+        self.0[0]
+    }
+
+    #[inline]
+    fn r(&self) -> f32 {
+        self.0[1]
+    }
+
+    #[inline]
+    fn s(&self) -> f32 {
+        self.0[2]
+    }
+
+    fn reflect_q(&self) -> Self {
+        Self([self.q(), self.s(), self.r()])
+    }
+
+    fn reflect_r(&self) -> Self {
+        Self([self.s(), self.r(), self.q()])
+    }
+
+    fn reflect_s(&self) -> Self {
+        Self([self.r(), self.q(), self.s()])
+    }
+
+    fn rotate_cw(&self, steps: usize) -> Self {
+        let (q, r, s) = self.qrs();
+        match steps % 6 {
+            0 => Self([q, r, s]),
+            1 => Self([-r, -s, -q]),
+            2 => Self([s, q, r]),
+            3 => Self([-q, -r, -s]),
+            4 => Self([r, s, q]),
+            _ => Self([-s, -q, -r]),
+        }
+    }
+
+    fn rotate_ccw(&self, steps: usize) -> Self {
+        self.rotate_cw(6 - steps % 6)
+    }
+
+    fn rotate_around(&self, pivot: Self, steps: i32) -> Self {
+        let steps = usize::try_from(steps.rem_euclid(6)).unwrap();
+        pivot + (*self - pivot).rotate_cw(steps)
+    }
+}
+
+impl From<HexCoord> for FracHexCoord {
+    fn from(value: HexCoord) -> Self {
+        let (q, r, s) = value.qrs_f32();
+        Self([q, r, s])
+    }
+}
+
+impl Display for FracHexCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{},{})", self.0[0], self.0[1], self.0[2])
+    }
+}
+
+impl Add for FracHexCoord {
+    type Output = FracHexCoord;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self([
+            self.0[0] + rhs.0[0],
+            self.0[1] + rhs.0[1],
+            self.0[2] + rhs.0[2],
+        ])
+    }
+}
+
+impl Sub for FracHexCoord {
+    type Output = FracHexCoord;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self([
+            self.0[0] - rhs.0[0],
+            self.0[1] - rhs.0[1],
+            self.0[2] - rhs.0[2],
+        ])
+    }
+}
+
+impl Mul<f32> for FracHexCoord {
+    type Output = FracHexCoord;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self([self.0[0] * rhs, self.0[1] * rhs, self.0[2] * rhs])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn from_hex_coord_is_lossless() {
+        let coord = HexCoord::from_axial(3, -7);
+        let frac: FracHexCoord = coord.into();
+        assert_eq!(frac, FracHexCoord::new(3.0, -7.0, 4.0));
+        assert_eq!(frac.round(), coord);
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(
+            FracHexCoord::from_axial(0.5, 0.25) + FracHexCoord::from_axial(1.0, -1.0),
+            FracHexCoord::from_axial(1.5, -0.75)
+        );
+    }
+
+    #[test]
+    fn sub() {
+        assert_eq!(
+            FracHexCoord::from_axial(0.5, 0.25) - FracHexCoord::from_axial(1.0, -1.0),
+            FracHexCoord::from_axial(-0.5, 1.25)
+        );
+    }
+
+    #[test]
+    fn mul() {
+        assert_eq!(FracHexCoord::from_axial(0.5, -1.0) * 2.0, FracHexCoord::from_axial(1.0, -2.0));
+    }
+
+    #[test_case(0.0, FracHexCoord::from_axial(0.0, 0.0))]
+    #[test_case(0.5, FracHexCoord::from_axial(1.0, -0.5))]
+    #[test_case(1.0, FracHexCoord::from_axial(2.0, -1.0))]
+    fn lerp(t: f32, expected: FracHexCoord) {
+        let a = FracHexCoord::from_axial(0.0, 0.0);
+        let b = FracHexCoord::from_axial(2.0, -1.0);
+        assert_eq!(a.lerp(b, t), expected);
+    }
+
+    #[test_case(FracHexCoord::from_axial(0.1, 0.1), HexCoord::from_axial(0, 0))]
+    #[test_case(FracHexCoord::from_axial(0.9, -0.2), HexCoord::from_axial(1, 0))]
+    #[test_case(FracHexCoord::from_axial(-1.6, 2.2), HexCoord::from_axial(-2, 2))]
+    fn round(frac: FracHexCoord, expected: HexCoord) {
+        assert_eq!(frac.round(), expected);
+    }
+
+    #[test]
+    fn dist() {
+        let a = FracHexCoord::from_axial(0.0, 0.0);
+        assert_eq!(a.dist(FracHexCoord::from_axial(1.5, 0.0)), 1.5);
+        assert_eq!(a.dist(FracHexCoord::from_axial(1.0, -1.0)), 1.0);
+    }
+
+    #[test]
+    fn ring_keeps_fractional_offset() {
+        let center = FracHexCoord::from_axial(0.25, 0.0);
+        let ring = center.ring(2);
+        assert_eq!(ring.len(), 12);
+        assert!(ring.iter().all(|c| (c.dist(center) - 2.0).abs() < f32::EPSILON));
+    }
+
+    #[test]
+    fn rotate_around() {
+        let pivot = FracHexCoord::from_axial(0.5, 0.0);
+        let c = FracHexCoord::from_axial(1.5, 0.0);
+        assert_eq!(c.rotate_around(pivot, 1), FracHexCoord::from_axial(0.5, 1.0));
+        assert_eq!(c.rotate_around(pivot, -1), FracHexCoord::from_axial(1.5, -1.0));
+    }
+
+    #[test]
+    fn neighbour() {
+        let c = FracHexCoord::from_axial(0.5, 0.5);
+        assert_eq!(c.neighbour(Edge::R), FracHexCoord::from_axial(1.5, 0.5));
+    }
+
+    #[test]
+    fn diagonal_neighbour_matches_hex_coord() {
+        let c = HexCoord::from_axial(2, -1);
+        for d in crate::Diagonal::ALL {
+            assert_eq!(FracHexCoord::from(c).diagonal_neighbour(d), c.diagonal_neighbour(d).into());
+        }
+    }
+}
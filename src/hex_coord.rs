@@ -1,11 +1,20 @@
-use crate::{hex_trait::HexCoordinate, Edge};
+use crate::{
+    hex_iter::{RangeIter, RingIter, SpiralIter},
+    hex_trait::HexCoordinate,
+    Diagonal, Edge, FracHexCoord,
+};
 use core::fmt::Debug;
 use std::{
     fmt::Display,
     ops::{Add, Mul, Sub},
 };
 
-#[derive(Debug, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "[i32; 3]", into = "[i32; 3]")
+)]
 pub struct HexCoord([i32; 3]);
 
 impl HexCoord {
@@ -18,6 +27,67 @@ impl HexCoord {
     pub fn from_axial(q: i32, r: i32) -> Self {
         Self::new(q, r, -q - r)
     }
+
+    #[inline]
+    pub fn ring_iter(self, radius: usize) -> RingIter {
+        RingIter::new(self, radius)
+    }
+
+    #[inline]
+    pub fn spiral_iter(self, radius: usize) -> SpiralIter {
+        SpiralIter::new(self, radius)
+    }
+
+    #[inline]
+    pub fn range_iter(self, radius: usize) -> RangeIter {
+        RangeIter::new(self, radius)
+    }
+
+    // Every cell on the straight line from self to other, inclusive of both ends.
+    // https://www.redblobgames.com/grids/hexagons/#line-drawing
+    pub fn line_to(self, other: HexCoord) -> impl Iterator<Item = HexCoord> {
+        // Nudge both ends off the cell edges so lines running exactly along an edge always
+        // resolve to the same side
+        self.line_with_nudge(other, FracHexCoord::new(1e-6, 2e-6, -3e-6))
+    }
+
+    pub(crate) fn line_with_nudge(
+        self,
+        other: HexCoord,
+        nudge: FracHexCoord,
+    ) -> impl Iterator<Item = HexCoord> {
+        // Interpolate the offset from self rather than absolute positions, far from the origin the
+        // nudge would otherwise be lost to f32 precision
+        let start = nudge;
+        let end = FracHexCoord::from(other - self) + nudge;
+        let steps = self.dist(other);
+
+        (0..=steps).map(move |i| match steps {
+            0 => self,
+            _ => self + start.lerp(end, i as f32 / steps as f32).round(),
+        })
+    }
+}
+
+// Rounds fractional cube coordinates to the nearest cell, resetting the component with the
+// largest rounding error so the result still satisfies q + r + s = 0.
+// https://www.redblobgames.com/grids/hexagons/#rounding
+pub(crate) fn cube_round(q: f32, r: f32, s: f32) -> HexCoord {
+    let (mut rq, mut rr, mut rs) = (q.round(), r.round(), s.round());
+
+    let dq = (rq - q).abs();
+    let dr = (rr - r).abs();
+    let ds = (rs - s).abs();
+
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    } else {
+        rs = -rq - rr;
+    }
+
+    HexCoord::new(rq as i32, rr as i32, rs as i32)
 }
 
 impl HexCoordinate<i32> for HexCoord {
@@ -38,17 +108,12 @@ impl HexCoordinate<i32> for HexCoord {
         self + edge.offset_flat()
     }
 
+    fn diagonal_neighbour(self, diagonal: Diagonal) -> Self {
+        self + diagonal.offset_flat()
+    }
+
     fn ring(self, radius: usize) -> Vec<Self> {
-        let mut result = Vec::with_capacity(radius * 6);
-
-        let mut coords = self + Edge::S.offset_flat() * radius.try_into().unwrap();
-        for e in 0..6 {
-            for _ in 0..radius {
-                result.push(coords);
-                coords = coords.neighbour(Edge::from_index(e))
-            }
-        }
-        result
+        self.ring_iter(radius).collect()
     }
 
     #[inline]
@@ -77,17 +142,50 @@ impl HexCoordinate<i32> for HexCoord {
     fn reflect_s(&self) -> Self {
         Self::new(self.r(), self.q(), self.s())
     }
+
+    fn rotate_cw(&self, steps: usize) -> Self {
+        let (q, r, s) = self.qrs();
+        match steps % 6 {
+            0 => Self([q, r, s]),
+            1 => Self([-r, -s, -q]),
+            2 => Self([s, q, r]),
+            3 => Self([-q, -r, -s]),
+            4 => Self([r, s, q]),
+            _ => Self([-s, -q, -r]),
+        }
+    }
+
+    fn rotate_ccw(&self, steps: usize) -> Self {
+        self.rotate_cw(6 - steps % 6)
+    }
+
+    fn rotate_around(&self, pivot: Self, steps: i32) -> Self {
+        let steps = usize::try_from(steps.rem_euclid(6)).unwrap();
+        pivot + (*self - pivot).rotate_cw(steps)
+    }
 }
 
-impl Display for HexCoord {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{},{})", self.0[0], self.0[1], self.0[2])
+impl TryFrom<[i32; 3]> for HexCoord {
+    type Error = String;
+
+    fn try_from(value: [i32; 3]) -> Result<Self, Self::Error> {
+        // Checked so out of range input is an error rather than an overflow
+        match value[0].checked_add(value[1]).and_then(|qr| qr.checked_add(value[2])) {
+            Some(0) => Ok(Self(value)),
+            _ => Err(format!("QRS must add up to 0, got {:?}", value)),
+        }
     }
 }
 
-impl PartialEq for HexCoord {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+impl From<HexCoord> for [i32; 3] {
+    fn from(value: HexCoord) -> Self {
+        value.0
+    }
+}
+
+impl Display for HexCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{},{})", self.0[0], self.0[1], self.0[2])
     }
 }
 
@@ -194,6 +292,114 @@ mod test {
         assert!(r.contains(&HexCoord::from_axial(0, -2)));
     }
 
+    #[test_case(0.0, 0.0, 0.0, HexCoord::from_axial(0, 0))]
+    #[test_case(0.4, -0.2, -0.2, HexCoord::from_axial(0, 0))]
+    #[test_case(0.6, -0.3, -0.3, HexCoord::from_axial(0, 0))]
+    #[test_case(0.8, -0.4, -0.4, HexCoord::from_axial(1, 0))]
+    #[test_case(1.4, 0.4, -1.8, HexCoord::from_axial(1, 1))]
+    #[test_case(-2.6, 1.8, 0.8, HexCoord::from_axial(-3, 2))]
+    fn cube_round_scenarios(q: f32, r: f32, s: f32, expected: HexCoord) {
+        assert_eq!(cube_round(q, r, s), expected);
+    }
+
+    #[test]
+    fn line_to_same_coord() {
+        let c = HexCoord::from_axial(2, -1);
+        assert_eq!(c.line_to(c).collect::<Vec<_>>(), vec![c]);
+    }
+
+    #[test]
+    fn line_to_straight_line() {
+        let line: Vec<_> = HexCoord::from_axial(0, 0).line_to(HexCoord::from_axial(3, 0)).collect();
+        assert_eq!(
+            line,
+            vec![
+                HexCoord::from_axial(0, 0),
+                HexCoord::from_axial(1, 0),
+                HexCoord::from_axial(2, 0),
+                HexCoord::from_axial(3, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_to_along_edge_is_deterministic() {
+        // (0,0) to (1,1) runs exactly between (1,0) and (0,1)
+        let line: Vec<_> = HexCoord::from_axial(0, 0).line_to(HexCoord::from_axial(1, 1)).collect();
+        assert_eq!(line.len(), 3);
+        assert_eq!(line[1], HexCoord::from_axial(0, 1));
+    }
+
+    #[test_case(HexCoord::from_axial(150, -90))]
+    #[test_case(HexCoord::from_axial(-4000, 2500))]
+    #[test_case(HexCoord::from_axial(1_000_000, 0))]
+    fn line_to_is_translation_invariant(offset: HexCoord) {
+        let a = HexCoord::from_axial(0, 0);
+        for b in [HexCoord::from_axial(1, 1), HexCoord::from_axial(3, -6), HexCoord::from_axial(-4, 2)] {
+            let near: Vec<_> = a.line_to(b).map(|c| c + offset).collect();
+            let far: Vec<_> = (a + offset).line_to(b + offset).collect();
+            assert_eq!(near, far);
+        }
+    }
+
+    #[test_case(0, 0, 5, -3)]
+    #[test_case(-4, 2, 7, 1)]
+    #[test_case(10, -10, -10, 10)]
+    fn line_to_steps_are_neighbours(q0: i32, r0: i32, q1: i32, r1: i32) {
+        let a = HexCoord::from_axial(q0, r0);
+        let b = HexCoord::from_axial(q1, r1);
+        let line: Vec<_> = a.line_to(b).collect();
+        assert_eq!(line.len() as i32, a.dist(b) + 1);
+        assert_eq!(line[0], a);
+        assert_eq!(line[line.len() - 1], b);
+        assert!(line.windows(2).all(|w| w[0].dist(w[1]) == 1));
+    }
+
+    #[test_case(0, HexCoord::from_axial(2, -1))]
+    #[test_case(1, HexCoord::from_axial(1, 1))]
+    #[test_case(2, HexCoord::from_axial(-1, 2))]
+    #[test_case(3, HexCoord::from_axial(-2, 1))]
+    #[test_case(6, HexCoord::from_axial(2, -1))]
+    fn rotate_cw(steps: usize, expected: HexCoord) {
+        assert_eq!(HexCoord::from_axial(2, -1).rotate_cw(steps), expected);
+    }
+
+    #[test_case(1)]
+    #[test_case(2)]
+    #[test_case(5)]
+    fn rotate_ccw_inverts_rotate_cw(steps: usize) {
+        let c = HexCoord::from_axial(3, -7);
+        assert_eq!(c.rotate_cw(steps).rotate_ccw(steps), c);
+    }
+
+    #[test]
+    fn rotate_cw_follows_edge_order() {
+        for i in 0..6 {
+            let e = Edge::from_index(i);
+            assert_eq!(e.offset_flat().rotate_cw(1), e.rotate_cw(1).offset_flat());
+        }
+    }
+
+    #[test_case(1)]
+    #[test_case(-1)]
+    #[test_case(4)]
+    #[test_case(-9)]
+    fn rotate_around_keeps_coord_and_edge_consistent(steps: i32) {
+        let pivot = HexCoord::from_axial(4, -2);
+        let coord = HexCoord::from_axial(1, 3);
+        let rotated = coord.rotate_around(pivot, steps);
+        assert_eq!(rotated.dist(pivot), coord.dist(pivot));
+
+        let edge_steps = usize::try_from(steps.rem_euclid(6)).unwrap();
+        for i in 0..6 {
+            let e = Edge::from_index(i);
+            assert_eq!(
+                coord.neighbour(e).rotate_around(pivot, steps),
+                rotated.neighbour(e.rotate_cw(edge_steps))
+            );
+        }
+    }
+
     #[test_case(0, 0, 5)]
     #[test_case(4, 5, 3)]
     #[test_case(-88,200,300)]
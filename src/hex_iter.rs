@@ -0,0 +1,234 @@
+use crate::{Edge, HexCoord, HexCoordinate};
+
+// Walks the cells at exactly `radius` from the center, starting along the S edge.
+// A radius of 0 yields nothing, matching `HexCoordinate::ring`.
+#[derive(Clone, Debug)]
+pub struct RingIter {
+    current: HexCoord,
+    radius: usize,
+    edge: usize,
+    step: usize,
+    remaining: usize,
+}
+
+impl RingIter {
+    pub(crate) fn new(center: HexCoord, radius: usize) -> Self {
+        Self {
+            current: center + Edge::S.offset_flat() * i32::try_from(radius).unwrap(),
+            radius,
+            edge: 0,
+            step: 0,
+            remaining: radius * 6,
+        }
+    }
+}
+
+impl Iterator for RingIter {
+    type Item = HexCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let result = self.current;
+        self.current = self.current.neighbour(Edge::from_index(self.edge));
+        self.step += 1;
+        if self.step == self.radius {
+            self.step = 0;
+            self.edge += 1;
+        }
+        self.remaining -= 1;
+
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for RingIter {}
+
+// Yields the center followed by each ring in turn out to `radius`, so cells come out in
+// order of distance from the center.
+#[derive(Clone, Debug)]
+pub struct SpiralIter {
+    center: HexCoord,
+    radius: usize,
+    ring: Option<RingIter>,
+    remaining: usize,
+}
+
+impl SpiralIter {
+    pub(crate) fn new(center: HexCoord, radius: usize) -> Self {
+        Self {
+            center,
+            radius,
+            ring: None,
+            remaining: range_area(radius),
+        }
+    }
+}
+
+impl Iterator for SpiralIter {
+    type Item = HexCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let ring = match &mut self.ring {
+            None => {
+                self.ring = Some(RingIter::new(self.center, 1));
+                return Some(self.center);
+            }
+            Some(ring) => ring,
+        };
+
+        match ring.next() {
+            Some(c) => Some(c),
+            None => {
+                let next_radius = ring.radius + 1;
+                debug_assert!(next_radius <= self.radius);
+                *ring = RingIter::new(self.center, next_radius);
+                ring.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for SpiralIter {}
+
+// Yields every cell within `radius` of the center, column by column.
+#[derive(Clone, Debug)]
+pub struct RangeIter {
+    center: HexCoord,
+    radius: i32,
+    q: i32,
+    r: i32,
+    remaining: usize,
+}
+
+impl RangeIter {
+    pub(crate) fn new(center: HexCoord, radius: usize) -> Self {
+        let n = i32::try_from(radius).unwrap();
+        Self {
+            center,
+            radius: n,
+            q: -n,
+            r: i32::MIN,
+            remaining: range_area(radius),
+        }
+    }
+}
+
+impl Iterator for RangeIter {
+    type Item = HexCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let n = self.radius;
+        let r_min = (-n).max(-self.q - n);
+        let r_max = n.min(-self.q + n);
+        if self.r < r_min {
+            self.r = r_min;
+        }
+
+        let result = self.center + HexCoord::from_axial(self.q, self.r);
+
+        if self.r == r_max {
+            self.q += 1;
+            self.r = i32::MIN;
+        } else {
+            self.r += 1;
+        }
+        self.remaining -= 1;
+
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for RangeIter {}
+
+#[inline]
+fn range_area(radius: usize) -> usize {
+    3 * radius * (radius + 1) + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    // Starts `radius` steps along S and walks the Q, QR, R, RS, S and QS edges in turn
+    #[test_case(0, &[])]
+    #[test_case(1, &[(-1, 1), (-1, 0), (0, -1), (1, -1), (1, 0), (0, 1)])]
+    #[test_case(2, &[
+        (-2, 2), (-2, 1), (-2, 0), (-1, -1), (0, -2), (1, -2),
+        (2, -2), (2, -1), (2, 0), (1, 1), (0, 2), (-1, 2),
+    ])]
+    fn ring_iter_walks_round_from_s(radius: usize, expected: &[(i32, i32)]) {
+        for center in [HexCoord::from_axial(0, 0), HexCoord::from_axial(3, -2)] {
+            let iter = center.ring_iter(radius);
+            assert_eq!(iter.len(), expected.len());
+            let expected: Vec<_> = expected.iter().map(|&(q, r)| center + HexCoord::from_axial(q, r)).collect();
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+            assert_eq!(center.ring(radius), expected);
+        }
+    }
+
+    #[test_case(0, 1)]
+    #[test_case(1, 7)]
+    #[test_case(3, 37)]
+    fn spiral_iter_yields_rings_in_order(radius: usize, expected: usize) {
+        let center = HexCoord::from_axial(-1, 5);
+        let iter = center.spiral_iter(radius);
+        assert_eq!(iter.len(), expected);
+
+        let cells: Vec<_> = iter.collect();
+        assert_eq!(cells.len(), expected);
+        assert_eq!(cells[0], center);
+        assert!(cells.windows(2).all(|w| w[0].dist(center) <= w[1].dist(center)));
+        assert_eq!(cells.iter().collect::<HashSet<_>>().len(), expected);
+    }
+
+    #[test_case(0, 1)]
+    #[test_case(1, 7)]
+    #[test_case(2, 19)]
+    #[test_case(5, 91)]
+    fn range_iter_covers_spiral(radius: usize, expected: usize) {
+        let center = HexCoord::from_axial(4, 4);
+        let iter = center.range_iter(radius);
+        assert_eq!(iter.len(), expected);
+
+        let range: HashSet<_> = iter.collect();
+        let spiral: HashSet<_> = center.spiral_iter(radius).collect();
+        assert_eq!(range.len(), expected);
+        assert_eq!(range, spiral);
+        let max = i32::try_from(radius).unwrap();
+        assert!(range.iter().all(|c| c.dist(center) <= max));
+    }
+
+    #[test]
+    fn len_decreases_while_iterating() {
+        let mut iter = HexCoord::from_axial(0, 0).spiral_iter(2);
+        iter.next();
+        iter.next();
+        assert_eq!(iter.len(), 17);
+    }
+}
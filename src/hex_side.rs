@@ -0,0 +1,116 @@
+/*
+   The side shared by two cells. Seen from either cell it is a different Edge, so it is stored
+   canonically as the Q, QR or R edge of one of them.
+*/
+
+use crate::{Edge, HexCoord, HexCoordinate, HexVertex};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct HexSide {
+    cell: HexCoord,
+    edge: u8,
+}
+
+impl HexSide {
+    pub fn new(cell: HexCoord, edge: Edge) -> Self {
+        match edge.index() {
+            i @ 0..=2 => Self { cell, edge: i as u8 },
+            i => Self {
+                cell: cell.neighbour(edge),
+                edge: (i - 3) as u8,
+            },
+        }
+    }
+
+    // All six sides of a cell, in Edge order
+    pub fn of_cell(cell: HexCoord) -> [HexSide; 6] {
+        [0, 1, 2, 3, 4, 5].map(|i| Self::new(cell, Edge::from_index(i)))
+    }
+
+    // The canonical cell, the side is either its Q, QR or R edge
+    #[inline]
+    pub fn cell(&self) -> HexCoord {
+        self.cell
+    }
+
+    #[inline]
+    pub fn edge(&self) -> Edge {
+        Edge::from_index(self.edge as usize)
+    }
+
+    // The two cells either side
+    pub fn cells(&self) -> [HexCoord; 2] {
+        [self.cell, self.cell.neighbour(self.edge())]
+    }
+
+    // The two end points, Edge i runs from corner i - 1 to corner i
+    pub fn vertices(&self) -> [HexVertex; 2] {
+        let i = self.edge as usize;
+        [HexVertex::new(self.cell, (i + 5) % 6), HexVertex::new(self.cell, i)]
+    }
+
+    // The four sides that share an end point with this one
+    pub fn neighbours(&self) -> [HexSide; 4] {
+        let i = self.edge as usize;
+        let edge = |j: usize| Edge::from_index(j % 6);
+        [
+            Self::new(self.cell, edge(i + 5)),
+            Self::new(self.cell.neighbour(edge(i + 5)), edge(i + 1)),
+            Self::new(self.cell, edge(i + 1)),
+            Self::new(self.cell.neighbour(edge(i)), edge(i + 2)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    #[test_case(HexCoord::from_axial(0, 0))]
+    #[test_case(HexCoord::from_axial(-4, 7))]
+    fn both_cells_give_the_same_side(cell: HexCoord) {
+        for i in 0..6 {
+            let edge = Edge::from_index(i);
+            let side = HexSide::new(cell, edge);
+            let other = cell.neighbour(edge);
+            assert_eq!(side, HexSide::new(other, edge.rotate_cw(3)));
+            assert!(side.cells().contains(&cell));
+            assert!(side.cells().contains(&other));
+        }
+    }
+
+    #[test]
+    fn cell_has_six_distinct_sides() {
+        let sides = HexSide::of_cell(HexCoord::from_axial(2, 2));
+        assert_eq!(sides.iter().collect::<HashSet<_>>().len(), 6);
+    }
+
+    #[test]
+    fn vertices_touch_both_cells() {
+        let cell = HexCoord::from_axial(1, -3);
+        for side in HexSide::of_cell(cell) {
+            let [a, b] = side.vertices();
+            assert_ne!(a, b);
+            assert!(a.adjacent().contains(&b));
+            for v in [a, b] {
+                assert!(side.cells().iter().all(|c| v.cells().contains(c)));
+            }
+        }
+    }
+
+    #[test]
+    fn neighbours_share_a_vertex() {
+        let cell = HexCoord::from_axial(0, 3);
+        for side in HexSide::of_cell(cell) {
+            let neighbours = side.neighbours();
+            assert_eq!(neighbours.iter().collect::<HashSet<_>>().len(), 4);
+            assert!(!neighbours.contains(&side));
+            for n in neighbours {
+                let shared = n.vertices().iter().filter(|v| side.vertices().contains(v)).count();
+                assert_eq!(shared, 1);
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Edge;
+use crate::{Diagonal, Edge};
 
 pub trait HexCoordinate<T> {
     fn q(&self) -> T;
@@ -8,8 +8,21 @@ pub trait HexCoordinate<T> {
     fn qrs_f32(&self) -> (f32,f32,f32);
     fn dist(&self, rhs: Self) -> T;
     fn neighbour(self, edge: Edge) -> Self;
+    // Two steps, along the edges either side of the diagonal
+    fn diagonal_neighbour(self, diagonal: Diagonal) -> Self
+    where
+        Self: Sized,
+    {
+        let [a, b] = diagonal.edges();
+        self.neighbour(a).neighbour(b)
+    }
     fn ring(self, radius: usize) -> Vec<Self> where Self: Sized;
     fn reflect_q(&self) -> Self;
     fn reflect_r(&self) -> Self;
     fn reflect_s(&self) -> Self;
+    // Rotations are in 60° steps around the origin, clockwise matching Edge index order
+    fn rotate_cw(&self, steps: usize) -> Self;
+    fn rotate_ccw(&self, steps: usize) -> Self;
+    // Positive steps rotate clockwise, negative counter-clockwise
+    fn rotate_around(&self, pivot: Self, steps: i32) -> Self;
 }
\ No newline at end of file
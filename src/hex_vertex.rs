@@ -0,0 +1,125 @@
+/*
+     5 _____ 0
+      /     \
+   4 /       \ 1
+     \       /
+    3 \_____/ 2
+
+   Corner i sits between Edge i and Edge i + 1. Each corner is shared by three cells, so it is
+   stored canonically as corner 0 or 1 of one of them.
+*/
+
+use crate::{Edge, HexCoord, HexCoordinate};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct HexVertex {
+    cell: HexCoord,
+    corner: u8,
+}
+
+impl HexVertex {
+    pub fn new(cell: HexCoord, corner: usize) -> Self {
+        match corner {
+            0 => Self { cell, corner: 0 },
+            1 => Self { cell, corner: 1 },
+            2 => Self { cell: cell.neighbour(Edge::RS), corner: 0 },
+            3 => Self { cell: cell.neighbour(Edge::S), corner: 1 },
+            4 => Self { cell: cell.neighbour(Edge::S), corner: 0 },
+            5 => Self { cell: cell.neighbour(Edge::QS), corner: 1 },
+            _ => panic!("Corner '{corner}' out of range"),
+        }
+    }
+
+    // All six corners of a cell, in corner order
+    pub fn of_cell(cell: HexCoord) -> [HexVertex; 6] {
+        [0, 1, 2, 3, 4, 5].map(|i| Self::new(cell, i))
+    }
+
+    // The canonical cell, the vertex is either its corner 0 or 1
+    #[inline]
+    pub fn cell(&self) -> HexCoord {
+        self.cell
+    }
+
+    #[inline]
+    pub fn corner(&self) -> usize {
+        self.corner as usize
+    }
+
+    // The three cells touching this vertex
+    pub fn cells(&self) -> [HexCoord; 3] {
+        let i = self.corner();
+        [
+            self.cell,
+            self.cell.neighbour(Edge::from_index(i)),
+            self.cell.neighbour(Edge::from_index(i + 1)),
+        ]
+    }
+
+    // The three vertices one side away from this one
+    pub fn adjacent(&self) -> [HexVertex; 3] {
+        let i = self.corner();
+        [
+            Self::new(self.cell, (i + 5) % 6),
+            Self::new(self.cell, i + 1),
+            Self::new(self.cell.neighbour(Edge::from_index(i)), i + 1),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    #[test_case(HexCoord::from_axial(0, 0))]
+    #[test_case(HexCoord::from_axial(3, -5))]
+    fn cell_has_six_distinct_vertices(cell: HexCoord) {
+        let vertices = HexVertex::of_cell(cell);
+        assert_eq!(vertices.iter().collect::<HashSet<_>>().len(), 6);
+        assert!(vertices.iter().all(|v| v.cells().contains(&cell)));
+    }
+
+    #[test]
+    fn shared_corners_are_equal() {
+        let c = HexCoord::from_axial(1, 1);
+        // Corner 1 of c is corner 5 of its R neighbour and corner 3 of its QR neighbour
+        assert_eq!(HexVertex::new(c, 1), HexVertex::new(c.neighbour(Edge::R), 5));
+        assert_eq!(HexVertex::new(c, 1), HexVertex::new(c.neighbour(Edge::QR), 3));
+    }
+
+    #[test]
+    fn every_corner_is_shared_by_three_cells() {
+        let c = HexCoord::from_axial(-2, 4);
+        for i in 0..6 {
+            let v = HexVertex::new(c, i);
+            let cells = v.cells();
+            for cell in cells {
+                assert!(HexVertex::of_cell(cell).contains(&v));
+            }
+            assert!(cells.iter().all(|a| cells.iter().all(|b| a.dist(*b) <= 1)));
+        }
+    }
+
+    #[test]
+    fn adjacent_vertices_share_two_cells() {
+        let c = HexCoord::from_axial(2, -1);
+        for i in 0..6 {
+            let v = HexVertex::new(c, i);
+            let adjacent = v.adjacent();
+            assert_eq!(adjacent.iter().collect::<HashSet<_>>().len(), 3);
+            for a in adjacent {
+                let shared = a.cells().iter().filter(|x| v.cells().contains(x)).count();
+                assert_eq!(shared, 2);
+                assert!(a.adjacent().contains(&v));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn corner_out_of_range_panics() {
+        HexVertex::new(HexCoord::from_axial(0, 0), 6);
+    }
+}
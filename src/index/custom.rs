@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use crate::{HexCoord, HexCoordinate, HexOrientation, HexWorldShape, OffsetCoord, OffsetParity};
+
+use super::Indexer;
+
+/*
+   An arbitrary set of cells. Membership is a bitset over the axial bounding box, one bit per
+   (q, r) row by row. The rank of a cell, i.e. the number of set bits before it, is its index, so
+   every real cell gets exactly one slot and holes get none. Ranks are a prefix sum per 64 bit
+   word plus a popcount within the word.
+
+   The mask is shared, cloning it or an indexer built from it doesn't copy the cells. The bounding
+   box is limited to u32::MAX cells, like the rank table.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<HexCoord>", into = "Vec<HexCoord>")
+)]
+pub struct HexMask(Arc<MaskData>);
+
+#[derive(Debug, PartialEq, Eq)]
+struct MaskData {
+    min_q: i64,
+    min_r: i64,
+    width: i64,
+    height: i64,
+    bits: Vec<u64>,
+    ranks: Vec<u32>,
+    cells: Vec<HexCoord>,
+    center: HexCoord,
+}
+
+impl HexMask {
+    // Duplicate cells are ignored. An empty mask has no cells and is centered on the origin.
+    pub fn new<I: IntoIterator<Item = HexCoord>>(cells: I) -> Self {
+        Self::try_new(cells).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // Fails if the cells are spread too far apart to fit the bounding box
+    pub fn try_new<I: IntoIterator<Item = HexCoord>>(cells: I) -> Result<Self, String> {
+        let mut cells: Vec<HexCoord> = cells.into_iter().collect();
+        cells.sort_by_key(|c| (c.r(), c.q()));
+        cells.dedup();
+
+        let (min_q, min_r, width, height) = match (cells.first(), cells.last()) {
+            (Some(first), Some(last)) => {
+                let min_q = i64::from(cells.iter().map(|c| c.q()).min().unwrap());
+                let max_q = i64::from(cells.iter().map(|c| c.q()).max().unwrap());
+                let (min_r, max_r) = (i64::from(first.r()), i64::from(last.r()));
+                (min_q, min_r, max_q - min_q + 1, max_r - min_r + 1)
+            }
+            _ => (0, 0, 0, 0),
+        };
+        let area = width
+            .checked_mul(height)
+            .filter(|a| *a <= i64::from(u32::MAX))
+            .ok_or_else(|| format!("Mask bounding box of {width}x{height} cells is too large"))?;
+
+        let mut bits = vec![0u64; usize::try_from(area).unwrap().div_ceil(64)];
+        for c in &cells {
+            let bit = usize::try_from((i64::from(c.r()) - min_r) * width + i64::from(c.q()) - min_q).unwrap();
+            bits[bit / 64] |= 1 << (bit % 64);
+        }
+
+        let ranks = bits
+            .iter()
+            .scan(0u32, |total, word| {
+                let rank = *total;
+                *total += word.count_ones();
+                Some(rank)
+            })
+            .collect();
+
+        // The cell closest to the mean position
+        let n = cells.len().max(1) as f32;
+        let (sq, sr) = cells.iter().fold((0.0, 0.0), |(q, r), c| (q + c.q() as f32, r + c.r() as f32));
+        let mean = crate::hex_coord::cube_round(sq / n, sr / n, -(sq + sr) / n);
+        let center = cells.iter().copied().min_by_key(|c| c.dist(mean)).unwrap_or(mean);
+
+        Ok(Self(Arc::new(MaskData {
+            min_q,
+            min_r,
+            width,
+            height,
+            bits,
+            ranks,
+            cells,
+            center,
+        })))
+    }
+
+    // The cells of `shape` for which `keep` is true
+    pub fn from_shape<F>(shape: HexWorldShape, keep: F) -> Self
+    where
+        F: Fn(HexCoord) -> bool,
+    {
+        Self::new(shape.coords().filter(|c| keep(*c)))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, coord: HexCoord) -> bool {
+        self.rank(coord).is_some()
+    }
+
+    // Cells ordered by r then q
+    pub fn cells(&self) -> &[HexCoord] {
+        &self.0.cells
+    }
+
+    fn rank(&self, coord: HexCoord) -> Option<usize> {
+        let m = &self.0;
+        let (q, r) = (i64::from(coord.q()) - m.min_q, i64::from(coord.r()) - m.min_r);
+        if !(0..m.width).contains(&q) || !(0..m.height).contains(&r) {
+            return None;
+        }
+        let bit = usize::try_from(r * m.width + q).unwrap();
+        let word = m.bits[bit / 64];
+        let below = word & ((1 << (bit % 64)) - 1);
+        if word & (1 << (bit % 64)) == 0 {
+            None
+        } else {
+            Some(m.ranks[bit / 64] as usize + below.count_ones() as usize)
+        }
+    }
+}
+
+impl TryFrom<Vec<HexCoord>> for HexMask {
+    type Error = String;
+
+    fn try_from(value: Vec<HexCoord>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+impl From<HexMask> for Vec<HexCoord> {
+    fn from(value: HexMask) -> Self {
+        value.0.cells.clone()
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomIndexer {
+    mask: HexMask,
+    orientation: HexOrientation,
+}
+
+impl CustomIndexer {
+    pub fn new(mask: HexMask, orientation: HexOrientation) -> Self {
+        Self { mask, orientation }
+    }
+}
+
+impl Indexer for CustomIndexer {
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.mask.len()
+    }
+
+    #[inline]
+    fn index(&self, coords: HexCoord) -> usize {
+        self.try_index(coords).unwrap()
+    }
+
+    #[inline]
+    fn try_index(&self, coords: HexCoord) -> Option<usize> {
+        self.mask.rank(coords)
+    }
+
+    #[inline]
+    fn coords(&self, index: usize) -> HexCoord {
+        self.mask.0.cells[index]
+    }
+
+    fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
+        OffsetCoord::new(col, row, OffsetParity::Odd).to_hex(self.orientation)
+    }
+
+    #[inline]
+    fn center(&self) -> HexCoord {
+        self.mask.0.center
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    fn ring_mask() -> HexMask {
+        HexMask::from_shape(HexWorldShape::Hexagon(6, HexOrientation::Flat), |c| {
+            c.dist(HexCoord::from_axial(0, 0)) % 2 == 1
+        })
+    }
+
+    #[test]
+    fn every_cell_has_one_slot() {
+        let indexer = CustomIndexer::new(ring_mask(), HexOrientation::Flat);
+        assert_eq!(indexer.capacity(), 6 + 18 + 30);
+        for i in 0..indexer.capacity() {
+            assert_eq!(indexer.try_index(indexer.coords(i)), Some(i));
+        }
+    }
+
+    #[test_case(HexCoord::from_axial(0, 0))]
+    #[test_case(HexCoord::from_axial(2, -1))]
+    #[test_case(HexCoord::from_axial(6, 0))]
+    #[test_case(HexCoord::from_axial(-100, 3))]
+    fn holes_and_outside_have_no_index(coords: HexCoord) {
+        let indexer = CustomIndexer::new(ring_mask(), HexOrientation::Flat);
+        assert_eq!(indexer.try_index(coords), None);
+    }
+
+    #[test]
+    fn duplicates_are_ignored() {
+        let a = HexCoord::from_axial(3, -7);
+        let b = HexCoord::from_axial(-2, 4);
+        let mask = HexMask::new([a, b, a, b, a]);
+        assert_eq!(mask.len(), 2);
+        assert!(mask.contains(a) && mask.contains(b));
+        assert!(!mask.contains(HexCoord::from_axial(0, 0)));
+    }
+
+    #[test]
+    fn ranks_span_words() {
+        // A long thin strip so the bitset needs several words
+        let mask = HexMask::new((0..200).filter(|q| q % 3 != 0).map(|q| HexCoord::from_axial(q, 0)));
+        let indexer = CustomIndexer::new(mask, HexOrientation::Pointy);
+        assert_eq!(indexer.try_index(HexCoord::from_axial(1, 0)), Some(0));
+        assert_eq!(indexer.try_index(HexCoord::from_axial(199, 0)), Some(132));
+        assert_eq!(indexer.try_index(HexCoord::from_axial(198, 0)), None);
+    }
+
+    #[test]
+    fn center_is_a_cell_of_the_mask() {
+        let indexer = CustomIndexer::new(ring_mask(), HexOrientation::Flat);
+        assert_eq!(indexer.center().dist(HexCoord::from_axial(0, 0)), 1);
+    }
+
+    #[test]
+    fn far_apart_cells_are_an_error() {
+        let cells = [HexCoord::from_axial(-100_000, 0), HexCoord::from_axial(100_000, 100_000)];
+        assert!(HexMask::try_new(cells).is_err());
+
+        // Sides that would overflow an i32 area
+        let cells = [HexCoord::from_axial(-(1 << 29), -(1 << 29)), HexCoord::from_axial(1 << 29, 1 << 29)];
+        assert!(HexMask::try_new(cells).is_err());
+    }
+
+    #[test]
+    fn empty_mask_has_no_cells() {
+        let indexer = CustomIndexer::new(HexMask::new([]), HexOrientation::Flat);
+        assert_eq!(indexer.capacity(), 0);
+        assert_eq!(indexer.try_index(HexCoord::from_axial(0, 0)), None);
+        assert_eq!(indexer.center(), HexCoord::from_axial(0, 0));
+    }
+}
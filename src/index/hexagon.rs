@@ -1,26 +1,64 @@
 use std::num::TryFromIntError;
 
-use crate::{HexCoord, HexCoordinate, HexOrientation};
+use crate::{HexCoord, HexCoordinate, HexOrientation, OffsetCoord, OffsetParity};
 
 use super::Indexer;
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "HexagonParams", into = "HexagonParams")
+)]
 pub struct HexagonIndexer {
     radius: usize,
     capacity: i32,
     shift: i32,
-    orientation: HexOrientation
+    orientation: HexOrientation,
+    wrapping: bool,
 }
 
 impl HexagonIndexer {
     pub fn new(radius: usize, orientation: HexOrientation) -> Self {
-        assert!(radius > 0);
-        Self {
+        Self::try_new(radius, orientation).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // Fails if the radius is zero or the area doesn't fit in an i32
+    pub fn try_new(radius: usize, orientation: HexOrientation) -> Result<Self, String> {
+        if radius == 0 {
+            return Err("Hexagon radius must be at least 1".to_string());
+        }
+        let r = i64::try_from(radius).map_err(|e| e.to_string())? - 1;
+        if 3 * r * r + 3 * r + 1 > i64::from(i32::MAX) {
+            return Err(format!("Hexagon radius {radius} is too large"));
+        }
+
+        Ok(Self {
             radius,
             capacity: hexagon_shape_area(radius), // #https://observablehq.com/@sanderevers/hexmod-representation
             shift: 3 * (i32::try_from(radius).unwrap() - 1) + 2,
-            orientation
-        }
+            orientation,
+            wrapping: false,
+        })
+    }
+
+    // A hexagon whose opposite sides are joined, hexmod indexing is already periodic so every
+    // coordinate lands on a cell
+    pub fn new_wrapping(radius: usize, orientation: HexOrientation) -> Self {
+        Self::try_new_wrapping(radius, orientation).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_new_wrapping(radius: usize, orientation: HexOrientation) -> Result<Self, String> {
+        Ok(Self {
+            wrapping: true,
+            ..Self::try_new(radius, orientation)?
+        })
+    }
+
+    // One of the six translations that map the hexagon onto its neighbouring copies
+    fn wrap_translation(&self) -> HexCoord {
+        let n = i32::try_from(self.radius).unwrap() - 1;
+        HexCoord::new(2 * n + 1, -n, -n - 1)
     }
 }
 
@@ -35,31 +73,101 @@ impl Indexer for HexagonIndexer {
         self.try_index(coords).unwrap()
     }
 
+    // Flat and pointy hexagons hold the same cube coordinates, only drawn rotated by 30 degrees,
+    // so hexmod indexes both orientations the same way
+    // hexmod is periodic, so outside a wrapping hexagon every coordinate would alias onto a cell
     #[inline]
     fn try_index(&self, coords: HexCoord) -> Option<usize> {
-        match self.orientation {
-            HexOrientation::Flat => hex_mod(coords, self.shift, self.capacity).ok(),
-            HexOrientation::Pointy => todo!(),
+        // The distance to the center is the largest of |q|, |r| and |s|, compared one by one as it
+        // overflows far out
+        let radius = u32::try_from(self.radius).unwrap();
+        let (q, r, s) = coords.qrs();
+        if !self.wrapping && [q, r, s].iter().any(|c| c.unsigned_abs() >= radius) {
+            return None;
         }
+        hex_mod(coords, self.shift, self.capacity).ok()
     }
 
     #[inline]
     fn coords(&self, index: usize) -> HexCoord {
-        match self.orientation {
-            HexOrientation::Flat => inv_hex_mod(index, self.shift, i32::try_from(self.radius - 1).unwrap()),
-            HexOrientation::Pointy => todo!(),
+        inv_hex_mod(index, self.shift, i32::try_from(self.radius - 1).unwrap())
+    }
+
+    // Offset coordinates for hexagons are odd r/q relative to the center cell, matching rectangles
+    fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
+        OffsetCoord::new(col, row, OffsetParity::Odd).to_hex(self.orientation)
+    }
+
+    #[inline]
+    fn center(&self) -> HexCoord {
+        HexCoord::new(0, 0, 0)
+    }
+
+    // A QR step adds one to q + s * shift, i.e. one to the index
+    #[inline]
+    fn contiguous_qr_rows(&self) -> bool {
+        true
+    }
+
+    fn canonical(&self, coord: HexCoord) -> HexCoord {
+        if !self.wrapping {
+            return coord;
         }
+        self.coords(hex_mod(coord, self.shift, self.capacity).unwrap())
     }
 
-    fn offset_coord(&self, _col: i32, _row: i32) -> HexCoord {
-        todo!()
+    fn nearest_image(&self, from: HexCoord, to: HexCoord) -> HexCoord {
+        if !self.wrapping {
+            return to;
+        }
+        let d = self.canonical(to) - self.canonical(from);
+        let t = self.wrap_translation();
+        let zero = HexCoord::new(0, 0, 0);
+        let best = (0..6)
+            .map(|i| d + t.rotate_cw(i))
+            .fold(d, |best, c| if c.dist(zero) < best.dist(zero) { c } else { best });
+        from + best
+    }
+}
+
+// Only the parameters are serialized, capacity and shift are rebuilt from them
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "HexagonIndexer")]
+struct HexagonParams {
+    radius: usize,
+    orientation: HexOrientation,
+    wrapping: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HexagonParams> for HexagonIndexer {
+    type Error = String;
+
+    fn try_from(value: HexagonParams) -> Result<Self, Self::Error> {
+        match value.wrapping {
+            true => Self::try_new_wrapping(value.radius, value.orientation),
+            false => Self::try_new(value.radius, value.orientation),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<HexagonIndexer> for HexagonParams {
+    fn from(value: HexagonIndexer) -> Self {
+        Self {
+            radius: value.radius,
+            orientation: value.orientation,
+            wrapping: value.wrapping,
+        }
     }
 }
 
 // https://observablehq.com/@sanderevers/hexmod-representation
+// In i64, s * shift overflows an i32 far out on wrapping hexagons
 fn hex_mod(coords: HexCoord, shift: i32, area: i32) -> Result<usize, TryFromIntError> {
     let (q, _, s) = coords.qrs();
-    let t = ((q + (s * shift)) + area) % area;
+    let t = (i64::from(q) + i64::from(s) * i64::from(shift)).rem_euclid(i64::from(area));
     usize::try_from(t)
 }
 
@@ -75,7 +183,7 @@ fn inv_hex_mod(index: usize, shift: i32, radius: i32) -> HexCoord {
 
 #[inline]
 fn hexagon_shape_area(radius: usize) -> i32 {
-    if radius <= 0 {
+    if radius == 0 {
         return 0;
     }
     let r = i32::try_from(radius).unwrap() - 1;
@@ -103,7 +211,6 @@ mod test {
     #[test_case(4)]
     #[test_case(5)]
     #[test_case(200)]
-
     pub fn hexagon_indexer_index_at_center_always_0(radius: usize) {
         let indexer = HexagonIndexer::new(radius, HexOrientation::Flat);
         assert_eq!(indexer.index(HexCoord::from_axial(0, 0)), 0)
@@ -140,9 +247,127 @@ mod test {
         assert_eq!(indexer.coords(indexer.index(coords)), coords);
     }
 
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    pub fn hexagon_indexer_round_trips_every_index(orientation: HexOrientation) {
+        for radius in 1..=12 {
+            let indexer = HexagonIndexer::new(radius, orientation);
+            let n = i32::try_from(radius).unwrap() - 1;
+            let mut seen = std::collections::HashSet::new();
+            for i in 0..indexer.capacity() {
+                let coords = indexer.coords(i);
+                assert!(coords.dist(HexCoord::from_axial(0, 0)) <= n, "Index {i} outside radius {radius}");
+                assert_eq!(indexer.index(coords), i, "Index {i} of radius {radius}");
+                assert!(seen.insert(coords));
+            }
+        }
+    }
+
+    #[test_case(HexOrientation::Flat, 0, 0, 0, 0)]
+    #[test_case(HexOrientation::Flat, 1, 1, 1, 1)]
+    #[test_case(HexOrientation::Flat, -1, -1, -1, 0)]
+    #[test_case(HexOrientation::Pointy, 0, 0, 0, 0)]
+    #[test_case(HexOrientation::Pointy, 1, 1, 1, 1)]
+    #[test_case(HexOrientation::Pointy, -1, -1, 0, -1)]
+    #[test_case(HexOrientation::Pointy, 2, -2, 3, -2)]
+    pub fn hexagon_indexer_offset_coord(
+        orientation: HexOrientation,
+        col: i32,
+        row: i32,
+        expected_q: i32,
+        expected_r: i32,
+    ) {
+        let indexer = HexagonIndexer::new(3, orientation);
+        assert_eq!(indexer.offset_coord(col, row), HexCoord::from_axial(expected_q, expected_r));
+    }
+
     #[test]
-    pub fn try_index_does_not_error() {
+    pub fn pointy_wrapping_canonical_matches_flat() {
+        let flat = HexagonIndexer::new_wrapping(4, HexOrientation::Flat);
+        let pointy = HexagonIndexer::new_wrapping(4, HexOrientation::Pointy);
+        for q in -10..=10 {
+            let coord = HexCoord::from_axial(q, 3 - q / 2);
+            assert_eq!(pointy.canonical(coord), flat.canonical(coord));
+        }
+    }
+
+    #[test_case(2)]
+    #[test_case(3)]
+    #[test_case(6)]
+    pub fn wrapping_canonical_is_inside_hexagon(radius: usize) {
+        let indexer = HexagonIndexer::new_wrapping(radius, HexOrientation::Flat);
+        let n = i32::try_from(radius).unwrap() - 1;
+        let zero = HexCoord::from_axial(0, 0);
+        for q in -20..=20 {
+            for r in -20..=20 {
+                let coord = HexCoord::from_axial(q, r);
+                let canonical = indexer.canonical(coord);
+                assert!(canonical.dist(zero) <= n);
+                assert_eq!(indexer.canonical(coord + indexer.wrap_translation().rotate_cw(2)), canonical);
+                if coord.dist(zero) <= n {
+                    assert_eq!(canonical, coord);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn wrapping_nearest_image_crosses_seam() {
+        let indexer = HexagonIndexer::new_wrapping(4, HexOrientation::Flat);
+        let from = HexCoord::from_axial(3, 0);
+        let to = HexCoord::from_axial(-3, 0);
+        let image = indexer.nearest_image(from, to);
+        assert_eq!(indexer.canonical(image), to);
+        assert!(image.dist(from) < to.dist(from));
+    }
+
+    #[test]
+    pub fn non_wrapping_canonical_is_identity() {
+        let indexer = HexagonIndexer::new(3, HexOrientation::Flat);
+        let far = HexCoord::from_axial(10, -4);
+        assert_eq!(indexer.canonical(far), far);
+        assert_eq!(indexer.nearest_image(HexCoord::from_axial(0, 0), far), far);
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    pub fn try_index_rejects_everything_outside(orientation: HexOrientation) {
+        let indexer = HexagonIndexer::new(4, orientation);
+        let zero = HexCoord::from_axial(0, 0);
+        for q in -10..=10 {
+            for r in -10..=10 {
+                let coord = HexCoord::from_axial(q, r);
+                assert_eq!(indexer.try_index(coord).is_some(), coord.dist(zero) <= 3);
+                assert_eq!(indexer.contains(coord), coord.dist(zero) <= 3);
+            }
+        }
+    }
+
+    #[test]
+    pub fn wrapping_contains_everything() {
+        let indexer = HexagonIndexer::new_wrapping(3, HexOrientation::Flat);
+        let far = HexCoord::from_axial(40, -17);
+        assert!(indexer.contains(far));
+        assert_eq!(indexer.try_index(far), indexer.try_index(indexer.canonical(far)));
+    }
+
+    #[test]
+    pub fn wrapping_far_coords_do_not_overflow() {
+        let indexer = HexagonIndexer::new_wrapping(100, HexOrientation::Flat);
+        let far = HexCoord::new(20_000_000, 0, -20_000_000);
+        let canonical = indexer.canonical(far);
+        assert!(canonical.dist(HexCoord::from_axial(0, 0)) < 100);
+        assert_eq!(indexer.canonical(far - indexer.wrap_translation()), canonical);
+        assert_eq!(indexer.try_index(far), indexer.try_index(canonical));
+    }
+
+    #[test_case(HexCoord::from_axial(100, 100))]
+    #[test_case(HexCoord::new(i32::MAX, 0, -i32::MAX))]
+    #[test_case(HexCoord::new(i32::MIN, i32::MAX, 1))]
+    #[test_case(HexCoord::new(1, i32::MIN, i32::MAX))]
+    pub fn try_index_does_not_error(coords: HexCoord) {
         let indexer = HexagonIndexer::new(3, HexOrientation::Flat);
-        assert_eq!(indexer.try_index(HexCoord::from_axial(100, 100)), None);
+        assert_eq!(indexer.try_index(coords), None);
+        assert!(HexagonIndexer::new_wrapping(3, HexOrientation::Flat).try_index(coords).is_some());
     }
 }
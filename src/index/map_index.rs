@@ -1,68 +1,188 @@
-#[cfg(feature = "bevy")]
-use bevy::log::debug;
-#[cfg(not(feature = "bevy"))]
-use log::debug;
-
 use crate::HexCoord;
 
-use super::{hexagon::HexagonIndexer, rectangle::RectangleIndexer, Indexer};
+use super::{
+    hexagon::HexagonIndexer, parallelogram::ParallelogramIndexer, rectangle::RectangleIndexer,
+    triangle::TriangleIndexer, Indexer,
+};
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MapIndex {
     Hexagon(HexagonIndexer),
     Rectangle(RectangleIndexer),
+    Parallelogram(ParallelogramIndexer),
+    Triangle(TriangleIndexer),
 }
 
 impl MapIndex {
     pub fn new(shape: crate::HexWorldShape) -> Self {
-        match shape {
-            crate::HexWorldShape::Hexagon(radius, orientation) => Self::Hexagon(HexagonIndexer::new(radius, orientation)),
+        Self::try_new(shape).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // Fails if the shape's dimensions can't be indexed
+    pub fn try_new(shape: crate::HexWorldShape) -> Result<Self, String> {
+        Ok(match shape {
+            crate::HexWorldShape::Hexagon(radius, orientation) => {
+                Self::Hexagon(HexagonIndexer::try_new(radius, orientation)?)
+            }
+            crate::HexWorldShape::WrappingHexagon(radius, orientation) => {
+                Self::Hexagon(HexagonIndexer::try_new_wrapping(radius, orientation)?)
+            }
             crate::HexWorldShape::Rectangle(width, height, orientation) => {
-                Self::Rectangle(RectangleIndexer::new(width, height, orientation))
+                Self::Rectangle(RectangleIndexer::try_new(width, height, orientation)?)
+            }
+            crate::HexWorldShape::WrappingRectangle(width, height, axis, orientation) => {
+                Self::Rectangle(RectangleIndexer::try_new_wrapping(width, height, axis, orientation)?)
             }
             crate::HexWorldShape::Square(width, orientation) => {
-                Self::Rectangle(RectangleIndexer::new(width, width, orientation))
+                Self::Rectangle(RectangleIndexer::try_new(width, width, orientation)?)
             }
-        }
+            crate::HexWorldShape::Parallelogram(width, height, axis, orientation) => {
+                Self::Parallelogram(ParallelogramIndexer::try_new(width, height, axis, orientation)?)
+            }
+            crate::HexWorldShape::Triangle(size, pointing) => Self::Triangle(TriangleIndexer::try_new(size, pointing)?),
+        })
     }
 
     #[inline]
-    pub fn index(self, coord: HexCoord) -> usize {
+    pub fn index(&self, coord: HexCoord) -> usize {
         match self {
             MapIndex::Hexagon(indexer) => indexer.index(coord),
             MapIndex::Rectangle(indexer) => indexer.index(coord),
+            MapIndex::Parallelogram(indexer) => indexer.index(coord),
+            MapIndex::Triangle(indexer) => indexer.index(coord),
         }
     }
-    #[inline]
 
-    pub fn try_index(self, coord: HexCoord) -> Option<usize> {
+    #[inline]
+    pub fn try_index(&self, coord: HexCoord) -> Option<usize> {
         match self {
             MapIndex::Hexagon(indexer) => indexer.try_index(coord),
             MapIndex::Rectangle(indexer) => indexer.try_index(coord),
+            MapIndex::Parallelogram(indexer) => indexer.try_index(coord),
+            MapIndex::Triangle(indexer) => indexer.try_index(coord),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, coord: HexCoord) -> bool {
+        match self {
+            MapIndex::Hexagon(indexer) => indexer.contains(coord),
+            MapIndex::Rectangle(indexer) => indexer.contains(coord),
+            MapIndex::Parallelogram(indexer) => indexer.contains(coord),
+            MapIndex::Triangle(indexer) => indexer.contains(coord),
         }
     }
 
     #[inline]
-    pub fn capacity(self) -> usize {
+    pub fn capacity(&self) -> usize {
         match self {
             MapIndex::Hexagon(indexer) => indexer.capacity(),
             MapIndex::Rectangle(indexer) => indexer.capacity(),
+            MapIndex::Parallelogram(indexer) => indexer.capacity(),
+            MapIndex::Triangle(indexer) => indexer.capacity(),
         }
     }
 
     #[inline]
-    pub fn coord(self, index: usize) -> HexCoord {
+    pub fn coord(&self, index: usize) -> HexCoord {
         match self {
             MapIndex::Hexagon(indexer) => indexer.coords(index),
             MapIndex::Rectangle(indexer) => indexer.coords(index),
+            MapIndex::Parallelogram(indexer) => indexer.coords(index),
+            MapIndex::Triangle(indexer) => indexer.coords(index),
         }
     }
 
     #[inline]
-    pub fn offset_coord(self, col: i32, row: i32) -> HexCoord {
+    pub fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
         match self {
             MapIndex::Hexagon(indexer) => indexer.offset_coord(col, row),
             MapIndex::Rectangle(indexer) => indexer.offset_coord(col, row),
+            MapIndex::Parallelogram(indexer) => indexer.offset_coord(col, row),
+            MapIndex::Triangle(indexer) => indexer.offset_coord(col, row),
         }
     }
+
+    #[inline]
+    pub fn center(&self) -> HexCoord {
+        match self {
+            MapIndex::Hexagon(indexer) => indexer.center(),
+            MapIndex::Rectangle(indexer) => indexer.center(),
+            MapIndex::Parallelogram(indexer) => indexer.center(),
+            MapIndex::Triangle(indexer) => indexer.center(),
+        }
+    }
+
+    #[inline]
+    pub fn canonical(&self, coord: HexCoord) -> HexCoord {
+        match self {
+            MapIndex::Hexagon(indexer) => indexer.canonical(coord),
+            MapIndex::Rectangle(indexer) => indexer.canonical(coord),
+            MapIndex::Parallelogram(indexer) => indexer.canonical(coord),
+            MapIndex::Triangle(indexer) => indexer.canonical(coord),
+        }
+    }
+
+    #[inline]
+    pub fn nearest_image(&self, from: HexCoord, to: HexCoord) -> HexCoord {
+        match self {
+            MapIndex::Hexagon(indexer) => indexer.nearest_image(from, to),
+            MapIndex::Rectangle(indexer) => indexer.nearest_image(from, to),
+            MapIndex::Parallelogram(indexer) => indexer.nearest_image(from, to),
+            MapIndex::Triangle(indexer) => indexer.nearest_image(from, to),
+        }
+    }
+}
+
+impl Indexer for MapIndex {
+    #[inline]
+    fn index(&self, coord: HexCoord) -> usize {
+        MapIndex::index(self, coord)
+    }
+
+    #[inline]
+    fn try_index(&self, coord: HexCoord) -> Option<usize> {
+        MapIndex::try_index(self, coord)
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        MapIndex::capacity(self)
+    }
+
+    #[inline]
+    fn coords(&self, index: usize) -> HexCoord {
+        self.coord(index)
+    }
+
+    #[inline]
+    fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
+        MapIndex::offset_coord(self, col, row)
+    }
+
+    #[inline]
+    fn contains(&self, coord: HexCoord) -> bool {
+        MapIndex::contains(self, coord)
+    }
+
+    #[inline]
+    fn center(&self) -> HexCoord {
+        MapIndex::center(self)
+    }
+
+    #[inline]
+    fn canonical(&self, coord: HexCoord) -> HexCoord {
+        MapIndex::canonical(self, coord)
+    }
+
+    #[inline]
+    fn nearest_image(&self, from: HexCoord, to: HexCoord) -> HexCoord {
+        MapIndex::nearest_image(self, from, to)
+    }
+
+    #[inline]
+    fn contiguous_qr_rows(&self) -> bool {
+        matches!(self, MapIndex::Hexagon(_))
+    }
 }
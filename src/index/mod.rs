@@ -1,16 +1,72 @@
-use crate::HexCoord;
+use crate::{Edge, HexCoord, HexCoordinate};
 
-mod hexagon;
+pub mod custom;
+pub mod hexagon;
 pub mod map_index;
-mod rectangle;
+pub mod parallelogram;
+pub mod rectangle;
+pub mod triangle;
 
 #[cfg(test)]
 mod tests;
 
+// Maps the cells of a shape onto 0..capacity. Implement it to store a StaticMap in a custom layout,
+// only the first five methods are required.
 pub trait Indexer {
     fn index(&self, coord: HexCoord) -> usize;
     fn try_index(&self, coord: HexCoord) -> Option<usize>;
     fn capacity(&self) -> usize;
     fn coords(&self, index: usize) -> HexCoord;
     fn offset_coord(&self, col: i32, row: i32) -> HexCoord;
+
+    // Whether the coordinate is a cell of the shape, on wrapping shapes every coordinate is
+    #[inline]
+    fn contains(&self, coord: HexCoord) -> bool {
+        self.try_index(coord).is_some()
+    }
+
+    // The cell in the middle of the shape
+    fn center(&self) -> HexCoord {
+        self.coords(self.capacity() / 2)
+    }
+
+    // Wrapping indexers map every coordinate onto one inside the shape, others leave it as is
+    fn canonical(&self, coord: HexCoord) -> HexCoord {
+        coord
+    }
+
+    // The copy of `to` closest to `from` when the shape wraps, otherwise `to`
+    fn nearest_image(&self, _from: HexCoord, to: HexCoord) -> HexCoord {
+        to
+    }
+
+    // Internal fast path, leave it false. Returning true promises that whenever `try_index` gives i
+    // for a cell c and some index for c + 2 * QR, then c + QR and c + 2 * QR are at (i + 1) and
+    // (i + 2) modulo capacity. StaticMap then reads the three cells straight from its store, if the
+    // promise is broken it reports the wrong cells or panics.
+    #[doc(hidden)]
+    fn contiguous_qr_rows(&self) -> bool {
+        false
+    }
+
+    // The following respect wrapping, on shapes that don't wrap they match HexCoordinate
+
+    #[inline]
+    fn neighbour(&self, coord: HexCoord, edge: Edge) -> HexCoord {
+        self.canonical(coord.neighbour(edge))
+    }
+
+    #[inline]
+    fn dist(&self, a: HexCoord, b: HexCoord) -> i32 {
+        a.dist(self.nearest_image(a, b))
+    }
+
+    // Cells can repeat once the ring is wider than a wrapping shape
+    fn ring(&self, center: HexCoord, radius: usize) -> Vec<HexCoord> {
+        center.ring_iter(radius).map(|c| self.canonical(c)).collect()
+    }
+
+    fn line(&self, a: HexCoord, b: HexCoord) -> impl Iterator<Item = HexCoord> + '_ {
+        a.line_to(self.nearest_image(a, b)).map(move |c| self.canonical(c))
+    }
 }
@@ -0,0 +1,171 @@
+use crate::{HexCoord, HexCoordinate, HexOrientation, OffsetCoord, OffsetParity, ParallelogramAxis};
+
+use super::Indexer;
+
+// Cells are stored column by column, the first coordinate of the axis pair picks the column and
+// the second the row within it. Both start at zero so every coordinate is non-negative.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ParallelogramParams", into = "ParallelogramParams")
+)]
+pub struct ParallelogramIndexer {
+    width: i32,
+    height: i32,
+    axis: ParallelogramAxis,
+    orientation: HexOrientation,
+}
+
+impl ParallelogramIndexer {
+    pub fn new(width: usize, height: usize, axis: ParallelogramAxis, orientation: HexOrientation) -> Self {
+        Self::try_new(width, height, axis, orientation).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // Fails if either side is zero or the area doesn't fit in an i32
+    pub fn try_new(
+        width: usize,
+        height: usize,
+        axis: ParallelogramAxis,
+        orientation: HexOrientation,
+    ) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("Parallelogram sides must be at least 1".to_string());
+        }
+        let too_large = || format!("Parallelogram {width}x{height} is too large");
+        let w = i32::try_from(width).map_err(|_| too_large())?;
+        let h = i32::try_from(height).map_err(|_| too_large())?;
+        w.checked_mul(h).ok_or_else(too_large)?;
+
+        Ok(Self {
+            width: w,
+            height: h,
+            axis,
+            orientation,
+        })
+    }
+
+    fn col_row(self, coord: HexCoord) -> (i32, i32) {
+        let (q, r, s) = coord.qrs();
+        match self.axis {
+            ParallelogramAxis::QR => (q, r),
+            ParallelogramAxis::RS => (r, s),
+            ParallelogramAxis::SQ => (s, q),
+        }
+    }
+
+    fn cell_at(self, col: i32, row: i32) -> HexCoord {
+        match self.axis {
+            ParallelogramAxis::QR => HexCoord::new(col, row, -col - row),
+            ParallelogramAxis::RS => HexCoord::new(-col - row, col, row),
+            ParallelogramAxis::SQ => HexCoord::new(row, -col - row, col),
+        }
+    }
+}
+
+// Serialized with unsigned sides so they are validated on the way back in
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "ParallelogramIndexer")]
+struct ParallelogramParams {
+    width: usize,
+    height: usize,
+    axis: ParallelogramAxis,
+    orientation: HexOrientation,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ParallelogramParams> for ParallelogramIndexer {
+    type Error = String;
+
+    fn try_from(value: ParallelogramParams) -> Result<Self, Self::Error> {
+        Self::try_new(value.width, value.height, value.axis, value.orientation)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ParallelogramIndexer> for ParallelogramParams {
+    fn from(value: ParallelogramIndexer) -> Self {
+        Self {
+            width: value.width as usize,
+            height: value.height as usize,
+            axis: value.axis,
+            orientation: value.orientation,
+        }
+    }
+}
+
+impl Indexer for ParallelogramIndexer {
+    #[inline]
+    fn capacity(&self) -> usize {
+        usize::try_from(self.width * self.height).unwrap()
+    }
+
+    #[inline]
+    fn index(&self, coords: HexCoord) -> usize {
+        self.try_index(coords).unwrap()
+    }
+
+    fn try_index(&self, coords: HexCoord) -> Option<usize> {
+        let (col, row) = self.col_row(coords);
+        if (0..self.width).contains(&col) && (0..self.height).contains(&row) {
+            usize::try_from(col * self.height + row).ok()
+        } else {
+            None
+        }
+    }
+
+    fn coords(&self, index: usize) -> HexCoord {
+        assert!(index < self.capacity());
+        let i = i32::try_from(index).unwrap();
+        self.cell_at(i / self.height, i % self.height)
+    }
+
+    fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
+        OffsetCoord::new(col, row, OffsetParity::Odd).to_hex(self.orientation)
+    }
+
+    fn center(&self) -> HexCoord {
+        self.cell_at((self.width - 1) / 2, (self.height - 1) / 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(ParallelogramAxis::QR)]
+    #[test_case(ParallelogramAxis::RS)]
+    #[test_case(ParallelogramAxis::SQ)]
+    fn index_round_trip(axis: ParallelogramAxis) {
+        let indexer = ParallelogramIndexer::new(4, 3, axis, HexOrientation::Flat);
+        assert_eq!(indexer.capacity(), 12);
+        for i in 0..indexer.capacity() {
+            assert_eq!(indexer.index(indexer.coords(i)), i);
+        }
+    }
+
+    #[test_case(ParallelogramAxis::QR, HexCoord::from_axial(3, 2))]
+    #[test_case(ParallelogramAxis::RS, HexCoord::new(-5, 3, 2))]
+    #[test_case(ParallelogramAxis::SQ, HexCoord::new(2, -5, 3))]
+    fn last_cell_is_far_corner(axis: ParallelogramAxis, expected: HexCoord) {
+        let indexer = ParallelogramIndexer::new(4, 3, axis, HexOrientation::Pointy);
+        assert_eq!(indexer.coords(11), expected);
+    }
+
+    #[test_case(HexCoord::from_axial(-1, 0))]
+    #[test_case(HexCoord::from_axial(0, 3))]
+    #[test_case(HexCoord::from_axial(4, 0))]
+    #[test_case(HexCoord::from_axial(100, 100))]
+    fn try_index_outside_is_none(coords: HexCoord) {
+        let indexer = ParallelogramIndexer::new(4, 3, ParallelogramAxis::QR, HexOrientation::Flat);
+        assert_eq!(indexer.try_index(coords), None);
+    }
+
+    #[test]
+    fn center_is_middle_cell() {
+        let indexer = ParallelogramIndexer::new(5, 3, ParallelogramAxis::QR, HexOrientation::Flat);
+        assert_eq!(indexer.center(), HexCoord::from_axial(2, 1));
+    }
+}
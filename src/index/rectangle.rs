@@ -1,22 +1,113 @@
-use crate::{HexCoord, HexCoordinate, HexOrientation};
+use crate::{HexCoord, HexCoordinate, HexOrientation, OffsetCoord, OffsetParity, WrapAxis};
 
 use super::Indexer;
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RectangleParams", into = "RectangleParams")
+)]
 pub struct RectangleIndexer {
+    width: i32,
     height: i32,
     capacity: i32,
     orientation: HexOrientation,
+    wrap: Option<WrapAxis>,
 }
 
 impl RectangleIndexer {
     pub fn new(width: usize, height: usize, orientation: HexOrientation) -> Self {
-        assert!(width > 0 && height > 0);
+        Self::try_new(width, height, orientation).unwrap_or_else(|e| panic!("{e}"))
+    }
 
-        Self {
-            height: height as i32,
-            capacity: (width * height) as i32,
+    // Fails if either side is zero or the area doesn't fit in an i32
+    pub fn try_new(width: usize, height: usize, orientation: HexOrientation) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("Rectangle sides must be at least 1".to_string());
+        }
+        let too_large = || format!("Rectangle {width}x{height} is too large");
+        let w = i32::try_from(width).map_err(|_| too_large())?;
+        let h = i32::try_from(height).map_err(|_| too_large())?;
+
+        Ok(Self {
+            width: w,
+            height: h,
+            capacity: w.checked_mul(h).ok_or_else(too_large)?,
             orientation,
+            wrap: None,
+        })
+    }
+
+    pub fn new_wrapping(width: usize, height: usize, axis: WrapAxis, orientation: HexOrientation) -> Self {
+        Self::try_new_wrapping(width, height, axis, orientation).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // A rectangle joined along one axis into a cylinder. The offset columns (flat) or rows (pointy)
+    // have to line up across the seam, so wrapping across them needs an even number.
+    pub fn try_new_wrapping(
+        width: usize,
+        height: usize,
+        axis: WrapAxis,
+        orientation: HexOrientation,
+    ) -> Result<Self, String> {
+        match (axis, orientation) {
+            (WrapAxis::Horizontal, HexOrientation::Flat) if !width.is_multiple_of(2) => {
+                return Err("Flat rectangles must have an even width to wrap horizontally".to_string());
+            }
+            (WrapAxis::Vertical, HexOrientation::Pointy) if !height.is_multiple_of(2) => {
+                return Err("Pointy rectangles must have an even height to wrap vertically".to_string());
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            wrap: Some(axis),
+            ..Self::try_new(width, height, orientation)?
+        })
+    }
+
+    // The translation that maps the rectangle onto its copy across the seam
+    fn wrap_translation(&self, axis: WrapAxis) -> HexCoord {
+        let (col, row) = match axis {
+            WrapAxis::Horizontal => (self.width, 0),
+            WrapAxis::Vertical => (0, self.height),
+        };
+        OffsetCoord::new(col, row, OffsetParity::Odd).to_hex(self.orientation)
+    }
+}
+
+// Only the parameters are serialized, the capacity is rebuilt from them
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "RectangleIndexer")]
+struct RectangleParams {
+    width: usize,
+    height: usize,
+    orientation: HexOrientation,
+    wrap: Option<WrapAxis>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RectangleParams> for RectangleIndexer {
+    type Error = String;
+
+    fn try_from(value: RectangleParams) -> Result<Self, Self::Error> {
+        match value.wrap {
+            Some(axis) => Self::try_new_wrapping(value.width, value.height, axis, value.orientation),
+            None => Self::try_new(value.width, value.height, value.orientation),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<RectangleIndexer> for RectangleParams {
+    fn from(value: RectangleIndexer) -> Self {
+        Self {
+            width: value.width as usize,
+            height: value.height as usize,
+            orientation: value.orientation,
+            wrap: value.wrap,
         }
     }
 }
@@ -33,19 +124,11 @@ impl Indexer for RectangleIndexer {
     }
 
     fn try_index(&self, coords: HexCoord) -> Option<usize> {
-        let row = match self.orientation {
-            HexOrientation::Flat => coords.r() + (coords.q() / 2),
-            HexOrientation::Pointy => coords.r(),
-        };
-        let column = match self.orientation {
-            HexOrientation::Flat => coords.q(),
-            HexOrientation::Pointy => coords.q() + (coords.r() / 2),
-        };
-        let i = (column * self.height) + row;
-        if i > self.capacity {
-            None
+        let offset = OffsetCoord::from_hex(self.canonical(coords), OffsetParity::Odd, self.orientation);
+        if (0..self.width).contains(&offset.col) && (0..self.height).contains(&offset.row) {
+            usize::try_from(offset.col * self.height + offset.row).ok()
         } else {
-            usize::try_from((column * self.height) + row).ok()
+            None
         }
     }
 
@@ -66,19 +149,34 @@ impl Indexer for RectangleIndexer {
     }
 
     // Since some of our shapes are regular, we can convert offset coordinates (col, row) to Hex Coordinates
-    // https://www.redblobgames.com/grids/hexagons/#conversions-offset
-    // Only supports odd r/q, where odd rows or columns are offset
+    // Rectangles are laid out as odd r/q, where odd rows or columns are offset. Use OffsetCoord for
+    // the other layouts.
     fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
-        match self.orientation {
-            HexOrientation::Flat => {
-                let r = row - (col - (col & 1)) / 2;
-                HexCoord::from_axial(col, r)
-            }
-            HexOrientation::Pointy => {
-                let q = col - (row - (row & 1)) / 2;
-                HexCoord::from_axial(q, row)
-            }
+        OffsetCoord::new(col, row, OffsetParity::Odd).to_hex(self.orientation)
+    }
+
+    fn canonical(&self, coord: HexCoord) -> HexCoord {
+        let Some(axis) = self.wrap else {
+            return coord;
+        };
+        let mut offset = OffsetCoord::from_hex(coord, OffsetParity::Odd, self.orientation);
+        match axis {
+            WrapAxis::Horizontal => offset.col = offset.col.rem_euclid(self.width),
+            WrapAxis::Vertical => offset.row = offset.row.rem_euclid(self.height),
         }
+        offset.to_hex(self.orientation)
+    }
+
+    fn nearest_image(&self, from: HexCoord, to: HexCoord) -> HexCoord {
+        let Some(axis) = self.wrap else {
+            return to;
+        };
+        // Move `to` into the same copy of the rectangle as `from`, then check either side of it
+        let image = self.canonical(to) + (from - self.canonical(from));
+        let t = self.wrap_translation(axis);
+        [image - t, image + t]
+            .into_iter()
+            .fold(image, |best, c| if c.dist(from) < best.dist(from) { c } else { best })
     }
 }
 
@@ -139,6 +237,67 @@ mod test {
         assert_eq!(indexer.index(coords), index);
     }
 
+    #[test_case(WrapAxis::Horizontal, HexOrientation::Flat)]
+    #[test_case(WrapAxis::Horizontal, HexOrientation::Pointy)]
+    #[test_case(WrapAxis::Vertical, HexOrientation::Flat)]
+    #[test_case(WrapAxis::Vertical, HexOrientation::Pointy)]
+    fn wrapping_canonical_lands_on_indexed_cell(axis: WrapAxis, orientation: HexOrientation) {
+        let indexer = RectangleIndexer::new_wrapping(6, 4, axis, orientation);
+        let t = indexer.wrap_translation(axis);
+        for i in 0..indexer.capacity() {
+            let coord = indexer.coords(i);
+            assert_eq!(indexer.canonical(coord), coord);
+            assert_eq!(indexer.canonical(coord + t), coord);
+            assert_eq!(indexer.canonical(coord - t * 3), coord);
+        }
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn horizontal_wrap_joins_first_and_last_columns(orientation: HexOrientation) {
+        let indexer = RectangleIndexer::new_wrapping(6, 4, WrapAxis::Horizontal, orientation);
+        let left = indexer.offset_coord(0, 1);
+        let right = indexer.offset_coord(5, 1);
+        let image = indexer.nearest_image(left, right);
+        assert_eq!(image.dist(left), 1);
+        assert_eq!(indexer.canonical(image), right);
+    }
+
+    #[test]
+    #[should_panic]
+    fn flat_horizontal_wrap_needs_even_width() {
+        RectangleIndexer::new_wrapping(5, 4, WrapAxis::Horizontal, HexOrientation::Flat);
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn try_index_rejects_everything_outside(orientation: HexOrientation) {
+        let indexer = RectangleIndexer::new(5, 4, orientation);
+        for col in -6..12 {
+            for row in -6..12 {
+                let coord = indexer.offset_coord(col, row);
+                let inside = (0..5).contains(&col) && (0..4).contains(&row);
+                assert_eq!(indexer.try_index(coord).is_some(), inside, "({col}, {row})");
+                assert_eq!(indexer.contains(coord), inside);
+            }
+        }
+    }
+
+    #[test]
+    fn row_beyond_height_does_not_alias_next_column() {
+        let indexer = RectangleIndexer::new(3, 3, HexOrientation::Flat);
+        assert_eq!(indexer.try_index(indexer.offset_coord(0, 3)), None);
+        assert_eq!(indexer.try_index(indexer.offset_coord(2, 3)), None);
+    }
+
+    #[test]
+    fn wrapping_only_rejects_the_unwrapped_axis() {
+        let indexer = RectangleIndexer::new_wrapping(6, 4, WrapAxis::Horizontal, HexOrientation::Flat);
+        assert_eq!(indexer.try_index(indexer.offset_coord(-1, 2)), indexer.try_index(indexer.offset_coord(5, 2)));
+        assert_eq!(indexer.try_index(indexer.offset_coord(2, 4)), None);
+        assert_eq!(indexer.try_index(indexer.offset_coord(2, -1)), None);
+    }
+
     #[test]
     pub fn try_index_does_not_error() {
         let indexer = RectangleIndexer::new(3, 3, HexOrientation::Flat);
@@ -0,0 +1,103 @@
+use crate::{Edge, HexCoord, HexCoordinate, HexOrientation, HexWorldShape, Indexer, MapIndex, WrapAxis};
+
+use test_case::test_case;
+
+// Wrapping
+#[test]
+pub fn wrapping_dist_is_symmetric_and_bounded() {
+    let index = MapIndex::new(HexWorldShape::WrappingHexagon(4, HexOrientation::Flat));
+    for i in 0..index.capacity() {
+        for j in 0..index.capacity() {
+            let (a, b) = (index.coord(i), index.coord(j));
+            assert_eq!(index.dist(a, b), index.dist(b, a));
+            assert!(index.dist(a, b) <= a.dist(b));
+            assert!(index.dist(a, b) <= 3);
+        }
+    }
+}
+
+#[test]
+pub fn wrapping_neighbour_is_canonical() {
+    let index = MapIndex::new(HexWorldShape::WrappingHexagon(3, HexOrientation::Flat));
+    for i in 0..index.capacity() {
+        let coord = index.coord(i);
+        for e in Edge::ALL {
+            let n = index.neighbour(coord, e);
+            assert_eq!(index.coord(index.index(n)), n);
+            assert_eq!(index.dist(coord, n), 1);
+        }
+    }
+}
+
+#[test]
+pub fn wrapping_line_crosses_seam() {
+    let index = MapIndex::new(HexWorldShape::WrappingHexagon(5, HexOrientation::Flat));
+    let a = HexCoord::from_axial(3, 0);
+    let b = HexCoord::from_axial(-3, 0);
+
+    let line: Vec<_> = index.line(a, b).collect();
+
+    assert_eq!(line.len() as i32, index.dist(a, b) + 1);
+    assert_eq!(line[0], a);
+    assert_eq!(line[line.len() - 1], b);
+    assert!(line.windows(2).all(|w| index.dist(w[0], w[1]) == 1));
+}
+
+#[test]
+pub fn wrapping_ring_is_canonical() {
+    let index = MapIndex::new(HexWorldShape::WrappingHexagon(4, HexOrientation::Flat));
+    let center = HexCoord::from_axial(3, -1);
+    let ring = index.ring(center, 2);
+    assert_eq!(ring.len(), 12);
+    assert!(ring.iter().all(|c| index.coord(index.index(*c)) == *c));
+    assert!(ring.iter().all(|c| index.dist(center, *c) == 2));
+}
+
+#[test_case(WrapAxis::Horizontal, HexOrientation::Flat)]
+#[test_case(WrapAxis::Horizontal, HexOrientation::Pointy)]
+#[test_case(WrapAxis::Vertical, HexOrientation::Flat)]
+#[test_case(WrapAxis::Vertical, HexOrientation::Pointy)]
+pub fn wrapping_rectangle_neighbour_is_canonical(axis: WrapAxis, orientation: HexOrientation) {
+    let index = MapIndex::new(HexWorldShape::WrappingRectangle(6, 4, axis, orientation));
+    for i in 0..index.capacity() {
+        let coord = index.coord(i);
+        // Neighbours across the unwrapped edges fall off the map
+        for n in Edge::ALL.map(|e| index.neighbour(coord, e)) {
+            if index.contains(n) {
+                assert_eq!(index.dist(coord, n), 1);
+            }
+        }
+    }
+}
+
+#[test_case(HexOrientation::Flat)]
+#[test_case(HexOrientation::Pointy)]
+pub fn wrapping_rectangle_dist_crosses_seam(orientation: HexOrientation) {
+    let index = MapIndex::new(HexWorldShape::WrappingRectangle(10, 4, WrapAxis::Horizontal, orientation));
+    let west = index.offset_coord(0, 1);
+    let east = index.offset_coord(9, 1);
+    assert_eq!(west.dist(east), 9);
+    assert_eq!(index.dist(west, east), 1);
+    assert_eq!(index.dist(east, west), 1);
+    // The seam doesn't shorten distances along the unwrapped axis
+    assert_eq!(index.dist(index.offset_coord(2, 0), index.offset_coord(2, 3)), 3);
+}
+
+#[test]
+pub fn non_wrapping_matches_hex_coordinate() {
+    let index = MapIndex::new(HexWorldShape::Hexagon(4, HexOrientation::Flat));
+    let a = HexCoord::from_axial(3, 0);
+    let b = HexCoord::from_axial(-3, 0);
+    assert_eq!(index.dist(a, b), a.dist(b));
+    assert_eq!(index.neighbour(a, Edge::R), a.neighbour(Edge::R));
+    assert_eq!(index.line(a, b).collect::<Vec<_>>(), a.line_to(b).collect::<Vec<_>>());
+}
+
+#[test_case(HexWorldShape::Hexagon(0, HexOrientation::Flat))]
+#[test_case(HexWorldShape::Rectangle(0, 3, HexOrientation::Pointy))]
+#[test_case(HexWorldShape::WrappingRectangle(3, 2, WrapAxis::Horizontal, HexOrientation::Flat))]
+#[test_case(HexWorldShape::Rectangle(65536, 65536, HexOrientation::Flat))]
+#[test_case(HexWorldShape::Hexagon(30000, HexOrientation::Flat))]
+fn try_new_rejects_invalid_shapes(shape: HexWorldShape) {
+    assert!(MapIndex::try_new(shape).is_err());
+}
@@ -0,0 +1,253 @@
+use crate::{
+    hex_coord::cube_round, HexCoord, HexCoordinate, OffsetCoord, OffsetParity, TrianglePointing,
+};
+
+use super::Indexer;
+
+/*
+   Triangles have their sides along the three axes, so flat hexagons make triangles pointing left
+   or right and pointy hexagons make triangles pointing up or down. With n = size both fit in the
+   q, r >= 0 quadrant:
+
+   Right / Down: q + r <= n - 1, with rows of r holding n, n - 1, ... 1 cells
+   Left / Up:    q, r <= n - 1 and q + r >= n - 1, with rows of r holding 1, 2, ... n cells
+
+   Cells are stored row by row. The second triangle is the first rotated a half turn, so it
+   indexes the first in reverse. The pointing fixes the orientation, see TrianglePointing.
+*/
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "TriangleParams", into = "TriangleParams")
+)]
+pub struct TriangleIndexer {
+    size: i32,
+    pointing: TrianglePointing,
+}
+
+impl TriangleIndexer {
+    pub fn new(size: usize, pointing: TrianglePointing) -> Self {
+        Self::try_new(size, pointing).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // Fails if the size is zero or the area doesn't fit in an i32
+    pub fn try_new(size: usize, pointing: TrianglePointing) -> Result<Self, String> {
+        if size == 0 {
+            return Err("Triangle size must be at least 1".to_string());
+        }
+        let too_large = || format!("Triangle size {size} is too large");
+        let n = i32::try_from(size).map_err(|_| too_large())?;
+        n.checked_add(1).and_then(|m| n.checked_mul(m)).ok_or_else(too_large)?;
+
+        Ok(Self { size: n, pointing })
+    }
+
+    #[inline]
+    fn reversed(&self) -> bool {
+        matches!(self.pointing, TrianglePointing::Left | TrianglePointing::Up)
+    }
+
+    // Half turn around the center of the triangle's bounding parallelogram
+    #[inline]
+    fn flip(&self, coord: HexCoord) -> HexCoord {
+        let n = self.size - 1;
+        HexCoord::from_axial(n - coord.q(), n - coord.r())
+    }
+
+    // Index in the right / down triangle
+    fn forward_index(&self, coord: HexCoord) -> Option<i32> {
+        let (q, r) = (coord.q(), coord.r());
+        if q < 0 || r < 0 || q + r >= self.size {
+            return None;
+        }
+        Some(r * self.size - r * (r - 1) / 2 + q)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "TriangleIndexer")]
+struct TriangleParams {
+    size: usize,
+    pointing: TrianglePointing,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TriangleParams> for TriangleIndexer {
+    type Error = String;
+
+    fn try_from(value: TriangleParams) -> Result<Self, Self::Error> {
+        Self::try_new(value.size, value.pointing)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TriangleIndexer> for TriangleParams {
+    fn from(value: TriangleIndexer) -> Self {
+        Self {
+            size: value.size as usize,
+            pointing: value.pointing,
+        }
+    }
+}
+
+impl Indexer for TriangleIndexer {
+    #[inline]
+    fn capacity(&self) -> usize {
+        usize::try_from(self.size * (self.size + 1) / 2).unwrap()
+    }
+
+    #[inline]
+    fn index(&self, coords: HexCoord) -> usize {
+        self.try_index(coords).unwrap()
+    }
+
+    fn try_index(&self, coords: HexCoord) -> Option<usize> {
+        let coords = if self.reversed() { self.flip(coords) } else { coords };
+        let i = self.forward_index(coords)?;
+        let i = if self.reversed() { self.capacity() as i32 - 1 - i } else { i };
+        usize::try_from(i).ok()
+    }
+
+    fn coords(&self, index: usize) -> HexCoord {
+        assert!(index < self.capacity());
+        let i = i32::try_from(index).unwrap();
+        let forward = if self.reversed() { self.capacity() as i32 - 1 - i } else { i };
+
+        // Counted from the end the rows grow 1, 2, ... so the row is a triangular root
+        let j = self.capacity() as i32 - 1 - forward;
+        let mut k = (((8.0 * j as f64 + 1.0).sqrt() - 1.0) / 2.0) as i32;
+        while k * (k + 1) / 2 > j {
+            k -= 1;
+        }
+        while (k + 1) * (k + 2) / 2 <= j {
+            k += 1;
+        }
+        let r = self.size - 1 - k;
+        let q = k - (j - k * (k + 1) / 2);
+
+        let coord = HexCoord::from_axial(q, r);
+        if self.reversed() {
+            self.flip(coord)
+        } else {
+            coord
+        }
+    }
+
+    fn offset_coord(&self, col: i32, row: i32) -> HexCoord {
+        OffsetCoord::new(col, row, OffsetParity::Odd).to_hex(self.pointing.orientation())
+    }
+
+    // The cell holding the centroid
+    fn center(&self) -> HexCoord {
+        let third = (self.size - 1) as f32 / 3.0;
+        let center = cube_round(third, third, -2.0 * third);
+        if self.reversed() {
+            self.flip(center)
+        } else {
+            center
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HexOrientation;
+    use test_case::test_case;
+
+    #[test_case(1, 1)]
+    #[test_case(3, 6)]
+    #[test_case(5, 15)]
+    fn capacity_is_triangular_number(size: usize, expected: usize) {
+        let indexer = TriangleIndexer::new(size, TrianglePointing::Up);
+        assert_eq!(indexer.capacity(), expected);
+    }
+
+    #[test_case(TrianglePointing::Right)]
+    #[test_case(TrianglePointing::Left)]
+    #[test_case(TrianglePointing::Down)]
+    #[test_case(TrianglePointing::Up)]
+    fn index_round_trip(pointing: TrianglePointing) {
+        let indexer = TriangleIndexer::new(7, pointing);
+        for i in 0..indexer.capacity() {
+            assert_eq!(indexer.try_index(indexer.coords(i)), Some(i));
+        }
+    }
+
+    #[test]
+    fn down_triangle_rows_shrink() {
+        let indexer = TriangleIndexer::new(3, TrianglePointing::Down);
+        let cells: Vec<_> = (0..6).map(|i| indexer.coords(i)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                HexCoord::from_axial(0, 0),
+                HexCoord::from_axial(1, 0),
+                HexCoord::from_axial(2, 0),
+                HexCoord::from_axial(0, 1),
+                HexCoord::from_axial(1, 1),
+                HexCoord::from_axial(0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn up_triangle_rows_grow() {
+        let indexer = TriangleIndexer::new(3, TrianglePointing::Up);
+        let cells: Vec<_> = (0..6).map(|i| indexer.coords(i)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                HexCoord::from_axial(2, 0),
+                HexCoord::from_axial(1, 1),
+                HexCoord::from_axial(2, 1),
+                HexCoord::from_axial(0, 2),
+                HexCoord::from_axial(1, 2),
+                HexCoord::from_axial(2, 2),
+            ]
+        );
+    }
+
+    #[test_case(TrianglePointing::Down, HexCoord::from_axial(2, 2))]
+    #[test_case(TrianglePointing::Down, HexCoord::from_axial(-1, 0))]
+    #[test_case(TrianglePointing::Up, HexCoord::from_axial(0, 0))]
+    #[test_case(TrianglePointing::Up, HexCoord::from_axial(3, 0))]
+    fn try_index_outside_is_none(pointing: TrianglePointing, coords: HexCoord) {
+        let indexer = TriangleIndexer::new(3, pointing);
+        assert_eq!(indexer.try_index(coords), None);
+    }
+
+    #[test_case(TrianglePointing::Down, HexCoord::from_axial(1, 1))]
+    #[test_case(TrianglePointing::Up, HexCoord::from_axial(2, 2))]
+    fn center_is_inside(pointing: TrianglePointing, expected: HexCoord) {
+        let indexer = TriangleIndexer::new(4, pointing);
+        assert_eq!(indexer.center(), expected);
+        assert!(indexer.try_index(expected).is_some());
+    }
+
+    #[test_case(TrianglePointing::Left, HexOrientation::Flat)]
+    #[test_case(TrianglePointing::Right, HexOrientation::Flat)]
+    #[test_case(TrianglePointing::Up, HexOrientation::Pointy)]
+    #[test_case(TrianglePointing::Down, HexOrientation::Pointy)]
+    fn pointing_decides_orientation(pointing: TrianglePointing, orientation: HexOrientation) {
+        let shape = crate::HexWorldShape::Triangle(3, pointing);
+        assert!(matches!(
+            (shape.orientation(), orientation),
+            (HexOrientation::Flat, HexOrientation::Flat) | (HexOrientation::Pointy, HexOrientation::Pointy)
+        ));
+    }
+
+    #[test]
+    fn zero_size_is_an_error() {
+        assert!(TriangleIndexer::try_new(0, TrianglePointing::Up).is_err());
+    }
+
+    #[test_case(100_000)]
+    #[test_case(i32::MAX as usize)]
+    #[test_case(usize::MAX)]
+    fn too_large_size_is_an_error(size: usize) {
+        assert!(TriangleIndexer::try_new(size, TrianglePointing::Up).is_err());
+    }
+}
@@ -1,21 +1,49 @@
+mod diagonal;
+mod direction;
 mod edge;
+mod frac_hex_coord;
 mod static_map;
 mod hex_coord;
+mod hex_iter;
+mod hex_side;
 mod hex_trait;
+mod hex_vertex;
 mod index;
+mod offset_coord;
 mod orientation;
+mod pathfinding;
+mod visibility;
 mod world;
 
 #[cfg(feature = "bevy")]
 pub mod bevy;
+#[cfg(feature = "serde")]
+mod serde;
 
+pub use crate::diagonal::Diagonal;
+pub use crate::direction::{FlatDirection, PointyDirection};
 pub use crate::edge::Edge;
+pub use crate::frac_hex_coord::FracHexCoord;
 pub use crate::static_map::*;
 pub use crate::hex_coord::HexCoord;
+pub use crate::hex_side::HexSide;
+pub use crate::hex_iter::{RangeIter, RingIter, SpiralIter};
 pub use crate::hex_trait::HexCoordinate;
-pub use crate::index::map_index::MapIndex;
+pub use crate::hex_vertex::HexVertex;
+pub use crate::index::{
+    custom::{CustomIndexer, HexMask},
+    hexagon::HexagonIndexer,
+    map_index::MapIndex,
+    parallelogram::ParallelogramIndexer,
+    rectangle::RectangleIndexer,
+    triangle::TriangleIndexer,
+    Indexer,
+};
+pub use crate::offset_coord::{DoubledCoord, OffsetCoord, OffsetParity};
 pub use crate::orientation::HexOrientation;
-pub use crate::world::{HexWorld, HexWorldShape};
+pub use crate::pathfinding::{distance_field, find_path, flow_field};
+pub use crate::visibility::{field_of_view, has_line_of_sight};
+pub use crate::world::{HexWorld, HexWorldShape, ParallelogramAxis, TrianglePointing, WrapAxis};
 
 #[cfg(feature = "bevy")]
 pub use crate::bevy::*;
@@ -24,11 +52,17 @@ pub use glam::{Vec2, Vec3};
 
 pub mod prelude {
     pub use crate::{
+        diagonal::Diagonal,
+        direction::{FlatDirection, PointyDirection},
         edge::Edge,
+        frac_hex_coord::FracHexCoord,
         hex_coord::HexCoord,
+        hex_side::HexSide,
         hex_trait::HexCoordinate,
-        index::map_index::MapIndex,
+        hex_vertex::HexVertex,
+        index::{custom::HexMask, map_index::MapIndex, Indexer},
+        offset_coord::{DoubledCoord, OffsetCoord, OffsetParity},
         orientation::HexOrientation,
-        world::{HexWorld, HexWorldShape}
+        world::{HexWorld, HexWorldShape, ParallelogramAxis, TrianglePointing, WrapAxis}
     };
 }
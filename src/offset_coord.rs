@@ -0,0 +1,140 @@
+use crate::{HexCoord, HexCoordinate, HexOrientation};
+
+// Which rows (pointy) or columns (flat) are shoved along by half a cell
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum OffsetParity {
+    Odd,
+    Even,
+}
+
+// Column/row coordinates as used by most level editors. The orientation decides the layout:
+// flat hexagons offset their columns (odd-q/even-q), pointy hexagons offset their rows (odd-r/even-r).
+// https://www.redblobgames.com/grids/hexagons/#coordinates-offset
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct OffsetCoord {
+    pub col: i32,
+    pub row: i32,
+    pub parity: OffsetParity,
+}
+
+impl OffsetCoord {
+    pub fn new(col: i32, row: i32, parity: OffsetParity) -> Self {
+        Self { col, row, parity }
+    }
+
+    pub fn from_hex(coord: HexCoord, parity: OffsetParity, orientation: HexOrientation) -> Self {
+        let (q, r, _) = coord.qrs();
+        match orientation {
+            HexOrientation::Flat => Self::new(q, r + shift(q, parity), parity),
+            HexOrientation::Pointy => Self::new(q + shift(r, parity), r, parity),
+        }
+    }
+
+    pub fn to_hex(self, orientation: HexOrientation) -> HexCoord {
+        match orientation {
+            HexOrientation::Flat => {
+                HexCoord::from_axial(self.col, self.row - shift(self.col, self.parity))
+            }
+            HexOrientation::Pointy => {
+                HexCoord::from_axial(self.col - shift(self.row, self.parity), self.row)
+            }
+        }
+    }
+}
+
+// Doubled coordinates step by two along the offset axis so every cell has a unique integer pair
+// without needing a parity. Flat hexagons double their rows, pointy hexagons double their columns.
+// https://www.redblobgames.com/grids/hexagons/#coordinates-doubled
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct DoubledCoord {
+    pub col: i32,
+    pub row: i32,
+}
+
+impl DoubledCoord {
+    pub fn new(col: i32, row: i32) -> Self {
+        Self { col, row }
+    }
+
+    pub fn from_hex(coord: HexCoord, orientation: HexOrientation) -> Self {
+        let (q, r, _) = coord.qrs();
+        match orientation {
+            HexOrientation::Flat => Self::new(q, 2 * r + q),
+            HexOrientation::Pointy => Self::new(2 * q + r, r),
+        }
+    }
+
+    pub fn to_hex(self, orientation: HexOrientation) -> HexCoord {
+        match orientation {
+            HexOrientation::Flat => {
+                assert_eq!((self.row - self.col) & 1, 0, "Doubled col and row must have equal parity");
+                HexCoord::from_axial(self.col, (self.row - self.col) / 2)
+            }
+            HexOrientation::Pointy => {
+                assert_eq!((self.col - self.row) & 1, 0, "Doubled col and row must have equal parity");
+                HexCoord::from_axial((self.col - self.row) / 2, self.row)
+            }
+        }
+    }
+}
+
+// How far a row (flat) or column (pointy) is pushed along by the offset of the other axis
+#[inline]
+fn shift(i: i32, parity: OffsetParity) -> i32 {
+    match parity {
+        OffsetParity::Odd => (i - (i & 1)) / 2,
+        OffsetParity::Even => (i + (i & 1)) / 2,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(OffsetParity::Odd, HexOrientation::Flat)]
+    #[test_case(OffsetParity::Even, HexOrientation::Flat)]
+    #[test_case(OffsetParity::Odd, HexOrientation::Pointy)]
+    #[test_case(OffsetParity::Even, HexOrientation::Pointy)]
+    fn offset_round_trip(parity: OffsetParity, orientation: HexOrientation) {
+        for col in -7..=7 {
+            for row in -7..=7 {
+                let offset = OffsetCoord::new(col, row, parity);
+                let hex = offset.to_hex(orientation);
+                assert_eq!(OffsetCoord::from_hex(hex, parity, orientation), offset);
+            }
+        }
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn doubled_round_trip(orientation: HexOrientation) {
+        for q in -7..=7 {
+            for r in -7..=7 {
+                let hex = HexCoord::from_axial(q, r);
+                assert_eq!(DoubledCoord::from_hex(hex, orientation).to_hex(orientation), hex);
+            }
+        }
+    }
+
+    // Reference values from https://www.redblobgames.com/grids/hexagons/#coordinates-offset
+    #[test_case(1, 1, OffsetParity::Odd, HexOrientation::Flat, HexCoord::from_axial(1, 1))]
+    #[test_case(1, 1, OffsetParity::Even, HexOrientation::Flat, HexCoord::from_axial(1, 0))]
+    #[test_case(3, 2, OffsetParity::Odd, HexOrientation::Flat, HexCoord::from_axial(3, 1))]
+    #[test_case(3, 2, OffsetParity::Even, HexOrientation::Flat, HexCoord::from_axial(3, 0))]
+    #[test_case(1, 1, OffsetParity::Odd, HexOrientation::Pointy, HexCoord::from_axial(1, 1))]
+    #[test_case(1, 1, OffsetParity::Even, HexOrientation::Pointy, HexCoord::from_axial(0, 1))]
+    #[test_case(2, 3, OffsetParity::Odd, HexOrientation::Pointy, HexCoord::from_axial(1, 3))]
+    #[test_case(2, 3, OffsetParity::Even, HexOrientation::Pointy, HexCoord::from_axial(0, 3))]
+    #[test_case(-1, -1, OffsetParity::Odd, HexOrientation::Flat, HexCoord::from_axial(-1, 0))]
+    fn offset_to_hex(col: i32, row: i32, parity: OffsetParity, orientation: HexOrientation, expected: HexCoord) {
+        assert_eq!(OffsetCoord::new(col, row, parity).to_hex(orientation), expected);
+    }
+
+    #[test_case(HexOrientation::Flat, HexCoord::from_axial(1, 1), 1, 3)]
+    #[test_case(HexOrientation::Pointy, HexCoord::from_axial(1, 1), 3, 1)]
+    #[test_case(HexOrientation::Flat, HexCoord::from_axial(-2, 1), -2, 0)]
+    fn doubled_from_hex(orientation: HexOrientation, hex: HexCoord, col: i32, row: i32) {
+        assert_eq!(DoubledCoord::from_hex(hex, orientation), DoubledCoord::new(col, row));
+    }
+}
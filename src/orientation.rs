@@ -10,8 +10,13 @@ const FA: f32 = SQRT_3;
 const FB: f32 = SQRT_3 / 2.0;
 const FC: f32 = 0.0;
 const FD: f32 = 3.0 / 2.0;
+const BA: f32 = SQRT_3 / 3.0;
+const BB: f32 = -1.0 / 3.0;
+const BC: f32 = 0.0;
+const BD: f32 = 2.0 / 3.0;
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HexOrientation {
     Flat,
     Pointy,
@@ -69,4 +74,37 @@ impl HexOrientation {
             HexOrientation::Flat => FA,
         }
     }
+
+    // Inverse of the f0..f3 matrix, used to convert world positions back to coordinates
+    #[inline]
+    pub fn b0(self) -> f32 {
+        match self {
+            HexOrientation::Pointy => BA,
+            HexOrientation::Flat => BD,
+        }
+    }
+
+    #[inline]
+    pub fn b1(self) -> f32 {
+        match self {
+            HexOrientation::Pointy => BB,
+            HexOrientation::Flat => BC,
+        }
+    }
+
+    #[inline]
+    pub fn b2(self) -> f32 {
+        match self {
+            HexOrientation::Pointy => BC,
+            HexOrientation::Flat => BB,
+        }
+    }
+
+    #[inline]
+    pub fn b3(self) -> f32 {
+        match self {
+            HexOrientation::Pointy => BD,
+            HexOrientation::Flat => BA,
+        }
+    }
 }
@@ -0,0 +1,339 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{Edge, HexCoord, Indexer, StaticMap};
+
+// Cost of entering each cell is given by `cost`, returning None for impassable cells. Bookkeeping
+// is kept in arrays sized to the map's capacity and only cells inside the map are ever visited.
+// Returns the path from start to goal inclusive along with its total cost.
+// `dist` is used as the heuristic, so paths are only guaranteed optimal when every passable cell
+// costs at least 1.
+pub fn find_path<T, I, F>(
+    map: &StaticMap<T, I>,
+    start: HexCoord,
+    goal: HexCoord,
+    cost: F,
+) -> Option<(Vec<HexCoord>, u32)>
+where
+    I: Indexer,
+    F: Fn(HexCoord, Option<&T>) -> Option<u32>,
+{
+    let index = map.index();
+    let start_i = index.try_index(start)?;
+    let goal_i = index.try_index(goal)?;
+    let goal = index.coords(goal_i);
+
+    let mut g_score = vec![u32::MAX; index.capacity()];
+    let mut came_from = vec![usize::MAX; index.capacity()];
+    let mut open = BinaryHeap::new();
+
+    g_score[start_i] = 0;
+    open.push(Reverse((heuristic(index, start, goal), 0, start_i)));
+
+    while let Some(Reverse((_, g, current_i))) = open.pop() {
+        if current_i == goal_i {
+            return Some((reconstruct(map, &came_from, start_i, goal_i), g));
+        }
+        // Stale entry, a cheaper route to this cell has already been expanded
+        if g > g_score[current_i] {
+            continue;
+        }
+
+        let current = index.coords(current_i);
+        for e in 0..6 {
+            let next = index.neighbour(current, Edge::from_index(e));
+            let Some(next_i) = index.try_index(next) else {
+                continue;
+            };
+            let Some(step) = cost(next, map.get(next)) else {
+                continue;
+            };
+
+            let tentative = g.saturating_add(step);
+            if tentative < g_score[next_i] {
+                g_score[next_i] = tentative;
+                came_from[next_i] = current_i;
+                open.push(Reverse((tentative.saturating_add(heuristic(index, next, goal)), tentative, next_i)));
+            }
+        }
+    }
+
+    None
+}
+
+// Cheapest cost from any of the sources to every reachable cell, using the same cost function as
+// `find_path`. Unreachable cells are left empty.
+pub fn distance_field<T, I, F, S>(map: &StaticMap<T, I>, sources: S, cost: F) -> StaticMap<u32, I>
+where
+    I: Indexer + Clone,
+    F: Fn(HexCoord, Option<&T>) -> Option<u32>,
+    S: IntoIterator<Item = HexCoord>,
+{
+    let index = map.index();
+    let mut distances = vec![u32::MAX; index.capacity()];
+    let mut open = BinaryHeap::new();
+
+    for i in sources.into_iter().filter_map(|s| index.try_index(s)) {
+        distances[i] = 0;
+        open.push(Reverse((0, i)));
+    }
+
+    while let Some(Reverse((d, current_i))) = open.pop() {
+        if d > distances[current_i] {
+            continue;
+        }
+
+        let current = index.coords(current_i);
+        for e in 0..6 {
+            let next = index.neighbour(current, Edge::from_index(e));
+            let Some(next_i) = index.try_index(next) else {
+                continue;
+            };
+            let Some(step) = cost(next, map.get(next)) else {
+                continue;
+            };
+
+            let tentative = d.saturating_add(step);
+            if tentative < distances[next_i] {
+                distances[next_i] = tentative;
+                open.push(Reverse((tentative, next_i)));
+            }
+        }
+    }
+
+    let mut field = StaticMap::with_index(index.clone());
+    for (i, d) in distances.into_iter().enumerate() {
+        if d != u32::MAX {
+            field.set_index(i, Some(d));
+        }
+    }
+    field
+}
+
+// The edge to step along from each cell to get closer to the nearest source of a distance field.
+// Sources and unreachable cells are left empty, ties go to the lowest edge index.
+pub fn flow_field<I: Indexer + Clone>(distances: &StaticMap<u32, I>) -> StaticMap<Edge, I> {
+    let index = distances.index();
+    let mut field = StaticMap::with_index(index.clone());
+
+    for (coord, d) in distances.coord_iter() {
+        let Some(&d) = d else {
+            continue;
+        };
+
+        let mut best: Option<(u32, Edge)> = None;
+        for e in 0..6 {
+            let edge = Edge::from_index(e);
+            if let Some(&nd) = distances.get(index.neighbour(coord, edge)) {
+                if nd < d && best.is_none_or(|(bd, _)| nd < bd) {
+                    best = Some((nd, edge));
+                }
+            }
+        }
+
+        if let Some((_, edge)) = best {
+            field.set(coord, Some(edge));
+        }
+    }
+
+    field
+}
+
+#[inline]
+fn heuristic<I: Indexer>(index: &I, a: HexCoord, b: HexCoord) -> u32 {
+    index.dist(a, b).unsigned_abs()
+}
+
+fn reconstruct<T, I: Indexer>(
+    map: &StaticMap<T, I>,
+    came_from: &[usize],
+    start_i: usize,
+    goal_i: usize,
+) -> Vec<HexCoord> {
+    let mut path = vec![map.index().coords(goal_i)];
+    let mut i = goal_i;
+    while i != start_i {
+        i = came_from[i];
+        path.push(map.index().coords(i));
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HexCoordinate, HexOrientation, HexWorldShape, WrapAxis};
+    use test_case::test_case;
+
+    fn uniform(_: HexCoord, v: Option<&bool>) -> Option<u32> {
+        match v {
+            Some(true) => None,
+            _ => Some(1),
+        }
+    }
+
+    fn open_map(shape: HexWorldShape) -> StaticMap<bool> {
+        StaticMap::new(shape)
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let map = open_map(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+        let c = HexCoord::from_axial(1, -1);
+        assert_eq!(find_path(&map, c, c, uniform), Some((vec![c], 0)));
+    }
+
+    #[test_case(HexCoord::from_axial(0, 0), HexCoord::from_axial(4, -2))]
+    #[test_case(HexCoord::from_axial(-4, 0), HexCoord::from_axial(4, 0))]
+    #[test_case(HexCoord::from_axial(2, 2), HexCoord::from_axial(-3, -1))]
+    fn open_map_path_is_shortest(start: HexCoord, goal: HexCoord) {
+        let map = open_map(HexWorldShape::Hexagon(6, HexOrientation::Flat));
+        let (path, cost) = find_path(&map, start, goal, uniform).unwrap();
+
+        assert_eq!(cost as i32, start.dist(goal));
+        assert_eq!(path.len() as i32, start.dist(goal) + 1);
+        assert_eq!(path[0], start);
+        assert_eq!(path[path.len() - 1], goal);
+        assert!(path.windows(2).all(|w| w[0].dist(w[1]) == 1));
+    }
+
+    #[test]
+    fn path_goes_around_wall() {
+        let mut map = open_map(HexWorldShape::Hexagon(5, HexOrientation::Flat));
+        for r in -3..=3 {
+            map.set(HexCoord::from_axial(0, r), Some(true));
+        }
+        let start = HexCoord::from_axial(-2, 1);
+        let goal = HexCoord::from_axial(2, -1);
+
+        let (path, cost) = find_path(&map, start, goal, uniform).unwrap();
+
+        assert!(cost as i32 > start.dist(goal));
+        assert!(path.iter().all(|c| map.bucket(*c).is_some()));
+        assert!(path.iter().all(|c| map.get(*c) != Some(&true)));
+    }
+
+    #[test]
+    fn weighted_cells_are_avoided() {
+        let mut map = open_map(HexWorldShape::Hexagon(4, HexOrientation::Flat));
+        map.set(HexCoord::from_axial(1, 0), Some(true));
+        let swamp = |_: HexCoord, v: Option<&bool>| match v {
+            Some(true) => Some(10),
+            _ => Some(1),
+        };
+
+        let (path, cost) = find_path(&map, HexCoord::from_axial(0, 0), HexCoord::from_axial(2, 0), swamp).unwrap();
+
+        assert_eq!(cost, 3);
+        assert!(!path.contains(&HexCoord::from_axial(1, 0)));
+    }
+
+    #[test]
+    fn unreachable_goal_is_none() {
+        let mut map = open_map(HexWorldShape::Hexagon(4, HexOrientation::Flat));
+        let goal = HexCoord::from_axial(1, 1);
+        for c in goal.ring(1) {
+            map.set(c, Some(true));
+        }
+        assert_eq!(find_path(&map, HexCoord::from_axial(-2, 0), goal, uniform), None);
+    }
+
+    #[test]
+    fn off_map_goal_is_none() {
+        let map = open_map(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+        assert_eq!(find_path(&map, HexCoord::from_axial(0, 0), HexCoord::from_axial(10, 0), uniform), None);
+    }
+
+    #[test]
+    fn distance_field_open_map_matches_dist() {
+        let map = open_map(HexWorldShape::Hexagon(5, HexOrientation::Flat));
+        let source = HexCoord::from_axial(1, -2);
+        let field = distance_field(&map, [source], uniform);
+
+        for (coord, d) in field.coord_iter() {
+            assert_eq!(*d.unwrap() as i32, coord.dist(source));
+        }
+    }
+
+    #[test]
+    fn distance_field_multiple_sources_takes_nearest() {
+        let map = open_map(HexWorldShape::Hexagon(5, HexOrientation::Flat));
+        let sources = [HexCoord::from_axial(-3, 0), HexCoord::from_axial(3, 0)];
+        let field = distance_field(&map, sources, uniform);
+
+        for (coord, d) in field.coord_iter() {
+            let expected = sources.iter().map(|s| coord.dist(*s)).min().unwrap();
+            assert_eq!(*d.unwrap() as i32, expected);
+        }
+    }
+
+    #[test]
+    fn distance_field_leaves_unreachable_empty() {
+        let mut map = open_map(HexWorldShape::Hexagon(4, HexOrientation::Flat));
+        let enclosed = HexCoord::from_axial(1, 1);
+        for c in enclosed.ring(1) {
+            map.set(c, Some(true));
+        }
+        let field = distance_field(&map, [HexCoord::from_axial(-2, 0)], uniform);
+
+        assert_eq!(field.get(enclosed), None);
+        assert_eq!(field.get(HexCoord::from_axial(1, 0)), None);
+        assert_eq!(field.get(HexCoord::from_axial(-2, 0)), Some(&0));
+    }
+
+    #[test]
+    fn flow_field_leads_to_source() {
+        let mut map = open_map(HexWorldShape::Hexagon(6, HexOrientation::Flat));
+        for r in -3..=4 {
+            map.set(HexCoord::from_axial(0, r), Some(true));
+        }
+        let source = HexCoord::from_axial(3, -1);
+        let distances = distance_field(&map, [source], uniform);
+        let flow = flow_field(&distances);
+
+        assert!(flow.get(source).is_none());
+        for (coord, d) in distances.coord_iter() {
+            let Some(&d) = d else { continue };
+            let mut current = coord;
+            for _ in 0..d {
+                current = current.neighbour(*flow.get(current).unwrap());
+            }
+            assert_eq!(current, source);
+        }
+    }
+
+    #[test]
+    fn wrapping_map_path_crosses_seam() {
+        let map = open_map(HexWorldShape::WrappingHexagon(5, HexOrientation::Flat));
+        let start = HexCoord::from_axial(4, 0);
+        let goal = HexCoord::from_axial(-4, 0);
+
+        let (path, cost) = find_path(&map, start, goal, uniform).unwrap();
+
+        assert_eq!(cost as i32, map.index().dist(start, goal));
+        assert!(cost < 8);
+        assert!(path.windows(2).all(|w| map.index().dist(w[0], w[1]) == 1));
+    }
+
+    #[test]
+    fn wrapping_rectangle_path_crosses_seam() {
+        let map = open_map(HexWorldShape::WrappingRectangle(10, 4, WrapAxis::Horizontal, HexOrientation::Flat));
+        let start = map.index().offset_coord(1, 2);
+        let goal = map.index().offset_coord(8, 2);
+
+        let (path, cost) = find_path(&map, start, goal, uniform).unwrap();
+
+        assert_eq!(cost, 3);
+        assert!(path.iter().all(|c| map.bucket(*c).is_some()));
+        assert!(path.windows(2).all(|w| map.index().dist(w[0], w[1]) == 1));
+    }
+
+    #[test]
+    fn rectangle_map_path() {
+        let map = open_map(HexWorldShape::Rectangle(6, 4, HexOrientation::Pointy));
+        let start = map.index().coords(0);
+        let goal = map.index().coords(map.index().capacity() - 1);
+        let (path, _) = find_path(&map, start, goal, uniform).unwrap();
+        assert!(path.iter().all(|c| map.bucket(*c).is_some()));
+    }
+}
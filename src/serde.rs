@@ -0,0 +1,232 @@
+#![cfg(feature = "serde")]
+
+// Types that hold derived state are serialized without it and rebuilt on the way back in.
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{HexWorld, HexWorldShape, Indexer, MapIndex, StaticMap};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "HexWorld")]
+struct HexWorldData<U> {
+    cell_size: U,
+    world_shape: HexWorldShape,
+}
+
+impl<U> Serialize for HexWorld<U>
+where
+    U: Copy + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HexWorldData {
+            cell_size: self.cell_size,
+            world_shape: self.world_shape,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, U> Deserialize<'de> for HexWorld<U>
+where
+    U: Copy + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = HexWorldData::deserialize(deserializer)?;
+        Ok(HexWorld {
+            cell_size: data.cell_size,
+            indexer: MapIndex::try_new(data.world_shape).map_err(D::Error::custom)?,
+            world_shape: data.world_shape,
+        })
+    }
+}
+
+// Values are stored in index order, the coordinates are implied by the indexer. Indexers are
+// rebuilt from their parameters, so the length check is against the real capacity.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "StaticMap")]
+struct StaticMapData<T, I> {
+    index: I,
+    values: Vec<Option<T>>,
+}
+
+impl<T, I> Serialize for StaticMap<T, I>
+where
+    T: Serialize,
+    I: Indexer + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StaticMapData {
+            index: self.index(),
+            values: self.coord_iter().map(|(_, v)| v).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T, I> Deserialize<'de> for StaticMap<T, I>
+where
+    T: Deserialize<'de>,
+    I: Indexer + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = StaticMapData::<T, I>::deserialize(deserializer)?;
+        if data.values.len() != data.index.capacity() {
+            return Err(D::Error::invalid_length(
+                data.values.len(),
+                &format!("{} values", data.index.capacity()).as_str(),
+            ));
+        }
+
+        let mut map = StaticMap::with_index(data.index);
+        for (i, v) in data.values.into_iter().enumerate() {
+            map.set_index(i, v);
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        CustomIndexer, Edge, HexCoord, HexMask, HexOrientation, HexWorld, HexWorldShape, HexagonIndexer, Indexer, MapIndex, StaticMap,
+        WrapAxis,
+    };
+    use test_case::test_case;
+
+    #[test]
+    fn hex_coord_round_trip() {
+        let coord = HexCoord::from_axial(3, -5);
+        let json = serde_json::to_string(&coord).unwrap();
+        assert_eq!(json, "[3,-5,2]");
+        assert_eq!(serde_json::from_str::<HexCoord>(&json).unwrap(), coord);
+    }
+
+    #[test]
+    fn hex_coord_rejects_invalid_qrs() {
+        assert!(serde_json::from_str::<HexCoord>("[1,1,1]").is_err());
+        assert!(serde_json::from_str::<HexCoord>("[2147483647,2147483647,2]").is_err());
+    }
+
+    #[test]
+    fn edge_round_trip() {
+        let json = serde_json::to_string(&Edge::RS).unwrap();
+        assert_eq!(serde_json::from_str::<Edge>(&json).unwrap().index(), Edge::RS.index());
+    }
+
+    #[test]
+    fn hex_world_round_trip() {
+        let world = HexWorld::new(HexWorldShape::Rectangle(4, 6, HexOrientation::Pointy), 2.5f32);
+        let json = serde_json::to_string(&world).unwrap();
+        let back: HexWorld<f32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.cell_size, 2.5);
+        assert_eq!(back.indexer.capacity(), 24);
+        assert_eq!(back.center(), world.center());
+    }
+
+    #[test]
+    fn static_map_round_trip() {
+        let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+        map.set(HexCoord::from_axial(1, -1), Some(4));
+        map.set(HexCoord::from_axial(-2, 0), Some(9));
+
+        let json = serde_json::to_string(&map).unwrap();
+        let back: StaticMap<u8> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            back.coord_iter().collect::<Vec<_>>(),
+            map.coord_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn custom_static_map_round_trip() {
+        let mask = HexMask::new([HexCoord::from_axial(4, 1), HexCoord::from_axial(-3, 2), HexCoord::from_axial(0, 0)]);
+        let mut map = StaticMap::with_index(CustomIndexer::new(mask, HexOrientation::Pointy));
+        map.set(HexCoord::from_axial(-3, 2), Some(5u8));
+
+        let json = serde_json::to_string(&map).unwrap();
+        let back: StaticMap<u8, CustomIndexer> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.index().capacity(), 3);
+        assert_eq!(back.index().try_index(HexCoord::from_axial(1, 0)), None);
+        assert_eq!(
+            back.coord_iter().collect::<Vec<_>>(),
+            map.coord_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn static_map_with_concrete_indexer_round_trip() {
+        let mut map = StaticMap::with_index(HexagonIndexer::new(2, HexOrientation::Pointy));
+        map.set(HexCoord::from_axial(0, 1), Some(3u8));
+
+        let json = serde_json::to_string(&map).unwrap();
+        let back: StaticMap<u8, HexagonIndexer> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            back.coord_iter().collect::<Vec<_>>(),
+            map.coord_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn owned_values_round_trip() {
+        let mut map: StaticMap<String> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+        map.insert(HexCoord::from_axial(0, 1), "keep".to_string());
+
+        let json = serde_json::to_string(&map).unwrap();
+        let back: StaticMap<String> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.get(HexCoord::from_axial(0, 1)).map(String::as_str), Some("keep"));
+    }
+
+    #[test]
+    fn indexer_derived_fields_are_rebuilt() {
+        let json = serde_json::to_value(HexagonIndexer::new(2, HexOrientation::Flat)).unwrap();
+        assert!(json.get("capacity").is_none());
+
+        let forged = r#"{"radius":2,"capacity":3,"shift":1,"orientation":"Flat","wrapping":false}"#;
+        let back: HexagonIndexer = serde_json::from_str(forged).unwrap();
+        assert_eq!(back.capacity(), 7);
+    }
+
+    #[test_case(r#"{"Hexagon":{"radius":0,"orientation":"Flat","wrapping":false}}"#)]
+    #[test_case(r#"{"Rectangle":{"width":3,"height":0,"orientation":"Flat","wrap":null}}"#)]
+    #[test_case(r#"{"Rectangle":{"width":3,"height":2,"orientation":"Flat","wrap":"Horizontal"}}"#)]
+    #[test_case(r#"{"Rectangle":{"width":65536,"height":65536,"orientation":"Flat","wrap":null}}"#)]
+    #[test_case(r#"{"Parallelogram":{"width":-1,"height":2,"axis":"QR","orientation":"Flat"}}"#)]
+    fn invalid_indexer_is_rejected(json: &str) {
+        assert!(serde_json::from_str::<MapIndex>(json).is_err());
+    }
+
+    #[test]
+    fn invalid_world_shape_is_rejected() {
+        let json = r#"{"cell_size":1.0,"world_shape":{"Hexagon":[0,"Flat"]}}"#;
+        assert!(serde_json::from_str::<HexWorld<f32>>(json).is_err());
+    }
+
+    #[test]
+    fn wrapping_rectangle_round_trip() {
+        let mut map: StaticMap<u8> =
+            StaticMap::new(HexWorldShape::WrappingRectangle(4, 3, WrapAxis::Horizontal, HexOrientation::Flat));
+        map.set(HexCoord::from_axial(1, 0), Some(2));
+
+        let json = serde_json::to_string(&map).unwrap();
+        let back: StaticMap<u8> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.get(HexCoord::from_axial(5, -2)), Some(&2));
+        assert_eq!(
+            back.coord_iter().collect::<Vec<_>>(),
+            map.coord_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn static_map_rejects_wrong_length() {
+        let map: StaticMap<u8> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+        let mut json: serde_json::Value = serde_json::to_value(&map).unwrap();
+        json["values"].as_array_mut().unwrap().pop();
+        assert!(serde_json::from_value::<StaticMap<u8>>(json).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{index::map_index::MapIndex, Edge, HexCoord, HexSide, HexVertex, HexWorldShape, Indexer};
+
+// Something shared between cells that can be keyed in an ElementMap. Every element has a
+// canonical cell and one of PER_CELL slots on it.
+pub trait MapElement: Copy + Eq + Hash {
+    const PER_CELL: usize;
+    fn canonical(&self) -> (HexCoord, usize);
+    fn from_canonical(cell: HexCoord, slot: usize) -> Self;
+    fn of_cell(cell: HexCoord) -> [Self; 6];
+}
+
+impl MapElement for HexSide {
+    const PER_CELL: usize = 3;
+
+    #[inline]
+    fn canonical(&self) -> (HexCoord, usize) {
+        (self.cell(), self.edge().index())
+    }
+
+    #[inline]
+    fn from_canonical(cell: HexCoord, slot: usize) -> Self {
+        HexSide::new(cell, Edge::from_index(slot))
+    }
+
+    #[inline]
+    fn of_cell(cell: HexCoord) -> [Self; 6] {
+        HexSide::of_cell(cell)
+    }
+}
+
+impl MapElement for HexVertex {
+    const PER_CELL: usize = 2;
+
+    #[inline]
+    fn canonical(&self) -> (HexCoord, usize) {
+        (self.cell(), self.corner())
+    }
+
+    #[inline]
+    fn from_canonical(cell: HexCoord, slot: usize) -> Self {
+        HexVertex::new(cell, slot)
+    }
+
+    #[inline]
+    fn of_cell(cell: HexCoord) -> [Self; 6] {
+        HexVertex::of_cell(cell)
+    }
+}
+
+// One value per side / corner of a world shape, i.e. every element touching at least one cell of
+// the shape. Elements whose canonical cell is inside the shape are indexed straight from the cell
+// index, the few on the outer boundary are numbered after them. Like StaticMap any Indexer can be
+// supplied through `with_index`.
+pub struct ElementMap<K: MapElement, T, I: Indexer = MapIndex> {
+    index: I,
+    outer: HashMap<K, usize>,
+    outer_keys: Vec<K>,
+    store: Vec<Option<T>>,
+}
+
+pub type EdgeMap<T, I = MapIndex> = ElementMap<HexSide, T, I>;
+pub type VertexMap<T, I = MapIndex> = ElementMap<HexVertex, T, I>;
+
+impl<K: MapElement, T> ElementMap<K, T> {
+    pub fn new(shape: HexWorldShape) -> Self {
+        Self::with_index(MapIndex::new(shape))
+    }
+}
+
+impl<K: MapElement, T, I: Indexer> ElementMap<K, T, I> {
+    pub fn with_index(index: I) -> Self {
+        let mut outer = HashMap::new();
+        let mut outer_keys = Vec::new();
+
+        for i in 0..index.capacity() {
+            for key in K::of_cell(index.coords(i)) {
+                let (cell, _) = key.canonical();
+                let key = canonical_key(&index, key);
+                if !index.contains(cell) && !outer.contains_key(&key) {
+                    outer.insert(key, outer_keys.len());
+                    outer_keys.push(key);
+                }
+            }
+        }
+
+        let mut store = Vec::new();
+        store.resize_with(index.capacity() * K::PER_CELL + outer_keys.len(), || None);
+
+        Self {
+            index,
+            outer,
+            outer_keys,
+            store,
+        }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.store.len()
+    }
+
+    pub fn index_of(&self, key: K) -> Option<usize> {
+        let (cell, slot) = key.canonical();
+        match self.index.try_index(cell) {
+            Some(i) => Some(i * K::PER_CELL + slot),
+            None => self
+                .outer
+                .get(&canonical_key(&self.index, key))
+                .map(|o| self.index.capacity() * K::PER_CELL + o),
+        }
+    }
+
+    pub fn key(&self, index: usize) -> K {
+        let inner = self.index.capacity() * K::PER_CELL;
+        if index < inner {
+            K::from_canonical(self.index.coords(index / K::PER_CELL), index % K::PER_CELL)
+        } else {
+            self.outer_keys[index - inner]
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, key: K) -> bool {
+        self.index_of(key).is_some()
+    }
+
+    pub fn get(&self, key: K) -> Option<&T> {
+        self.store.get(self.index_of(key)?)?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: K) -> Option<&mut T> {
+        let i = self.index_of(key)?;
+        self.store.get_mut(i)?.as_mut()
+    }
+
+    // Panics if the key doesn't touch the shape
+    pub fn set(&mut self, key: K, value: Option<T>) -> Option<T> {
+        let i = self.index_of(key).expect("Key is outside of the map");
+        std::mem::replace(&mut self.store[i], value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (K, Option<&T>)> {
+        self.store
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (self.key(i), v.as_ref()))
+    }
+
+    #[inline]
+    pub fn index(&self) -> &I {
+        &self.index
+    }
+}
+
+// On wrapping shapes a boundary element is reached from every image of the map, so it's keyed
+// by its canonical cell
+fn canonical_key<K: MapElement, I: Indexer>(index: &I, key: K) -> K {
+    let (cell, slot) = key.canonical();
+    K::from_canonical(index.canonical(cell), slot)
+}
+
+impl<T, I: Indexer> ElementMap<HexSide, T, I> {
+    #[inline]
+    pub fn get_edge(&self, cell: HexCoord, edge: Edge) -> Option<&T> {
+        self.get(HexSide::new(cell, edge))
+    }
+
+    #[inline]
+    pub fn set_edge(&mut self, cell: HexCoord, edge: Edge, value: Option<T>) -> Option<T> {
+        self.set(HexSide::new(cell, edge), value)
+    }
+}
+
+impl<T, I: Indexer> ElementMap<HexVertex, T, I> {
+    #[inline]
+    pub fn get_corner(&self, cell: HexCoord, corner: usize) -> Option<&T> {
+        self.get(HexVertex::new(cell, corner))
+    }
+
+    #[inline]
+    pub fn set_corner(&mut self, cell: HexCoord, corner: usize, value: Option<T>) -> Option<T> {
+        self.set(HexVertex::new(cell, corner), value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HexCoordinate, HexOrientation, TriangleIndexer, TrianglePointing, WrapAxis};
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    // Expected element counts for a hexagon of radius n: 9n^2 - 3n sides and 6n^2 vertices
+    #[test_case(1, 6, 6)]
+    #[test_case(2, 30, 24)]
+    #[test_case(3, 72, 54)]
+    fn hexagon_capacity(radius: usize, sides: usize, vertices: usize) {
+        let shape = HexWorldShape::Hexagon(radius, HexOrientation::Flat);
+        assert_eq!(EdgeMap::<u8>::new(shape).capacity(), sides);
+        assert_eq!(VertexMap::<u8>::new(shape).capacity(), vertices);
+    }
+
+    #[test_case(HexWorldShape::Hexagon(3, HexOrientation::Flat))]
+    #[test_case(HexWorldShape::WrappingHexagon(3, HexOrientation::Flat))]
+    #[test_case(HexWorldShape::Rectangle(4, 3, HexOrientation::Flat))]
+    #[test_case(HexWorldShape::Square(4, HexOrientation::Pointy))]
+    #[test_case(HexWorldShape::WrappingRectangle(6, 4, WrapAxis::Horizontal, HexOrientation::Flat))]
+    fn every_element_of_every_cell_has_a_unique_index(shape: HexWorldShape) {
+        let sides = EdgeMap::<u8>::new(shape);
+        let vertices = VertexMap::<u8>::new(shape);
+        let cells = MapIndex::new(shape);
+
+        let mut side_indices = HashSet::new();
+        let mut vertex_indices = HashSet::new();
+        for i in 0..cells.capacity() {
+            let cell = cells.coord(i);
+            for side in HexSide::of_cell(cell) {
+                let s = sides.index_of(side).unwrap();
+                assert_eq!(sides.index_of(sides.key(s)), Some(s));
+                side_indices.insert(s);
+            }
+            for vertex in HexVertex::of_cell(cell) {
+                let v = vertices.index_of(vertex).unwrap();
+                assert_eq!(vertices.index_of(vertices.key(v)), Some(v));
+                vertex_indices.insert(v);
+            }
+        }
+
+        assert_eq!(side_indices.len(), sides.capacity());
+        assert_eq!(vertex_indices.len(), vertices.capacity());
+    }
+
+    #[test_case(HexWorldShape::WrappingRectangle(6, 4, WrapAxis::Horizontal, HexOrientation::Flat), 6, 0)]
+    #[test_case(HexWorldShape::WrappingRectangle(6, 4, WrapAxis::Vertical, HexOrientation::Pointy), 0, 4)]
+    fn elements_of_a_shifted_image_have_the_same_index(shape: HexWorldShape, cols: i32, rows: i32) {
+        let sides = EdgeMap::<u8>::new(shape);
+        let vertices = VertexMap::<u8>::new(shape);
+        let cells = MapIndex::new(shape);
+        let period = cells.offset_coord(cols, rows) - cells.offset_coord(0, 0);
+
+        for i in 0..cells.capacity() {
+            let cell = cells.coord(i);
+            for image in [cell + period, cell - period] {
+                for (a, b) in HexSide::of_cell(cell).into_iter().zip(HexSide::of_cell(image)) {
+                    assert_eq!(sides.index_of(b), sides.index_of(a));
+                    assert!(sides.contains(b));
+                }
+                for (a, b) in HexVertex::of_cell(cell).into_iter().zip(HexVertex::of_cell(image)) {
+                    assert_eq!(vertices.index_of(b), vertices.index_of(a));
+                    assert!(vertices.contains(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wrapping_hexagon_has_no_boundary() {
+        let shape = HexWorldShape::WrappingHexagon(3, HexOrientation::Flat);
+        assert_eq!(EdgeMap::<u8>::new(shape).capacity(), 19 * 3);
+        assert_eq!(VertexMap::<u8>::new(shape).capacity(), 19 * 2);
+    }
+
+    #[test]
+    fn edge_is_shared_by_both_cells() {
+        let mut walls: EdgeMap<&str> = EdgeMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+        let cell = HexCoord::from_axial(1, 0);
+
+        assert_eq!(walls.set_edge(cell, Edge::S, Some("door")), None);
+        assert_eq!(walls.get_edge(cell.neighbour(Edge::S), Edge::QR), Some(&"door"));
+        assert_eq!(walls.set_edge(cell.neighbour(Edge::S), Edge::QR, Some("wall")), Some("door"));
+        assert_eq!(walls.iter().filter(|(_, v)| v.is_some()).count(), 1);
+    }
+
+    #[test]
+    fn boundary_elements_are_stored() {
+        let mut walls: EdgeMap<u8> = EdgeMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+        let cell = HexCoord::from_axial(-1, 1);
+        walls.set_edge(cell, Edge::S, Some(3));
+        *walls.get_mut(HexSide::new(cell, Edge::S)).unwrap() += 1;
+        assert_eq!(walls.get(HexSide::new(cell, Edge::S)), Some(&4));
+    }
+
+    #[test]
+    fn corner_is_shared_by_three_cells() {
+        let mut posts: VertexMap<u32> = VertexMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+        let vertex = HexVertex::new(HexCoord::from_axial(0, 0), 3);
+        posts.set(vertex, Some(7));
+
+        for cell in vertex.cells() {
+            let corner = (0..6).find(|&i| HexVertex::new(cell, i) == vertex).unwrap();
+            assert_eq!(posts.get_corner(cell, corner), Some(&7));
+        }
+    }
+
+    #[test]
+    fn concrete_indexer_matches_map_index() {
+        let shape = HexWorldShape::Triangle(4, TrianglePointing::Up);
+        let dynamic: EdgeMap<u8> = EdgeMap::new(shape);
+        let mut concrete = EdgeMap::with_index(TriangleIndexer::new(4, TrianglePointing::Up));
+        assert_eq!(concrete.capacity(), dynamic.capacity());
+
+        let cell = concrete.index().coords(0);
+        concrete.set_edge(cell, Edge::S, Some(1));
+        for i in 0..concrete.capacity() {
+            assert_eq!(concrete.key(i), dynamic.key(i));
+        }
+        assert_eq!(concrete.get(HexSide::new(cell, Edge::S)), Some(&1));
+    }
+
+    #[test]
+    fn elements_outside_the_shape_are_not_contained() {
+        let walls: EdgeMap<u8> = EdgeMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+        assert!(!walls.contains(HexSide::new(HexCoord::from_axial(5, 0), Edge::R)));
+        assert_eq!(walls.get(HexSide::new(HexCoord::from_axial(5, 0), Edge::R)), None);
+    }
+}
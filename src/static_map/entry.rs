@@ -0,0 +1,169 @@
+use crate::HexCoord;
+
+use super::CellBucket;
+
+// A view into a single cell of a StaticMap, from `StaticMap::entry`. Mirrors the HashMap entry API,
+// the coordinates are those the indexer stores for the cell.
+pub enum Entry<'a, T> {
+    Occupied(OccupiedEntry<'a, T>),
+    Vacant(VacantEntry<'a, T>),
+}
+
+pub struct OccupiedEntry<'a, T> {
+    coords: HexCoord,
+    bucket: &'a mut CellBucket<T>,
+    len: &'a mut usize,
+}
+
+pub struct VacantEntry<'a, T> {
+    coords: HexCoord,
+    bucket: &'a mut CellBucket<T>,
+    len: &'a mut usize,
+}
+
+impl<'a, T> Entry<'a, T> {
+    #[inline]
+    pub fn coords(&self) -> HexCoord {
+        match self {
+            Entry::Occupied(e) => e.coords,
+            Entry::Vacant(e) => e.coords,
+        }
+    }
+
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> T>(self, f: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(f()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(e) = &mut self {
+            f(e.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, T> OccupiedEntry<'a, T> {
+    pub(crate) fn new(coords: HexCoord, bucket: &'a mut CellBucket<T>, len: &'a mut usize) -> Self {
+        Self { coords, bucket, len }
+    }
+
+    #[inline]
+    pub fn coords(&self) -> HexCoord {
+        self.coords
+    }
+
+    pub fn get(&self) -> &T {
+        match &*self.bucket {
+            CellBucket::Occupied(_, v) => v,
+            CellBucket::Empty => unreachable!(),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        match self.bucket {
+            CellBucket::Occupied(_, v) => v,
+            CellBucket::Empty => unreachable!(),
+        }
+    }
+
+    pub fn into_mut(self) -> &'a mut T {
+        match self.bucket {
+            CellBucket::Occupied(_, v) => v,
+            CellBucket::Empty => unreachable!(),
+        }
+    }
+
+    // Returns the previous value
+    pub fn insert(&mut self, value: T) -> T {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> T {
+        *self.len -= 1;
+        match std::mem::take(self.bucket) {
+            CellBucket::Occupied(_, v) => v,
+            CellBucket::Empty => unreachable!(),
+        }
+    }
+}
+
+impl<'a, T> VacantEntry<'a, T> {
+    pub(crate) fn new(coords: HexCoord, bucket: &'a mut CellBucket<T>, len: &'a mut usize) -> Self {
+        Self { coords, bucket, len }
+    }
+
+    #[inline]
+    pub fn coords(&self) -> HexCoord {
+        self.coords
+    }
+
+    pub fn insert(self, value: T) -> &'a mut T {
+        *self.bucket = CellBucket::Occupied(self.coords, value);
+        *self.len += 1;
+        match self.bucket {
+            CellBucket::Occupied(_, v) => v,
+            CellBucket::Empty => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HexOrientation, HexWorldShape, StaticMap};
+
+    fn map() -> StaticMap<Vec<&'static str>> {
+        StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat))
+    }
+
+    #[test]
+    fn or_default_then_modify() {
+        let mut units = map();
+        let cell = HexCoord::from_axial(1, -1);
+
+        units.entry(cell).or_default().push("archer");
+        units.entry(cell).and_modify(|v| v.push("knight")).or_default();
+
+        assert_eq!(units.get(cell), Some(&vec!["archer", "knight"]));
+    }
+
+    #[test]
+    fn and_modify_skips_vacant() {
+        let mut units = map();
+        let cell = HexCoord::from_axial(0, 2);
+
+        let v = units.entry(cell).and_modify(|v| v.push("never")).or_insert_with(|| vec!["scout"]);
+        assert_eq!(v, &vec!["scout"]);
+    }
+
+    #[test]
+    fn occupied_entry_insert_and_remove() {
+        let mut units = map();
+        let cell = HexCoord::from_axial(-2, 1);
+        units.insert(cell, vec!["pike"]);
+
+        let Entry::Occupied(mut e) = units.entry(cell) else {
+            panic!("Expected an occupied entry");
+        };
+        assert_eq!(e.coords(), cell);
+        assert_eq!(e.insert(vec!["sword"]), vec!["pike"]);
+        assert_eq!(e.remove(), vec!["sword"]);
+
+        assert!(matches!(units.entry(cell), Entry::Vacant(_)));
+        assert_eq!(units.get(cell), None);
+    }
+}
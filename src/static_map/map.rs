@@ -1,20 +1,23 @@
-use std::{mem, slice::Iter};
-
-#[cfg(feature = "bevy")]
-use bevy::log::debug;
-#[cfg(not(feature = "bevy"))]
-use log::debug;
+use std::{
+    mem,
+    ops::{Index, IndexMut},
+    slice::Iter,
+};
 
 use crate::{
-    static_map::neighbourhood::NeighbourhoodNode, index::map_index::MapIndex, Edge, HexCoord,
-    HexCoordinate, HexWorldShape,
+    static_map::neighbourhood::NeighbourhoodNode, index::map_index::MapIndex, CustomIndexer, Diagonal, Edge, HexCoord,
+    HexCoordinate, HexMask, HexOrientation, HexWorldShape, Indexer,
 };
 
-use super::neighbourhood::Neighbourhood;
+use super::{
+    entry::{Entry, OccupiedEntry, VacantEntry},
+    neighbourhood::{ExtendedNeighbourhood, Neighbourhood},
+};
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Default)]
 pub enum CellBucket<T> {
     Occupied(HexCoord, T),
+    #[default]
     Empty,
 }
 
@@ -28,75 +31,172 @@ impl<T> CellBucket<T> {
     }
 }
 
-impl<T> Default for CellBucket<T> {
-    fn default() -> Self {
-        Self::Empty
+// Cells are stored in the order given by the indexer, MapIndex covers every HexWorldShape. Any
+// other Indexer can be supplied through `with_index`.
+pub struct StaticMap<T, I: Indexer = MapIndex> {
+    index: I,
+    store: Vec<CellBucket<T>>,
+    // Occupied cells
+    len: usize,
+}
+
+impl<T> StaticMap<T> {
+    pub fn new(shape: HexWorldShape) -> Self {
+        Self::with_index(MapIndex::new(shape))
     }
 }
 
-pub struct StaticMap<T> {
-    index: MapIndex,
-    store: Vec<CellBucket<T>>,
+impl<T: Clone> StaticMap<T> {
+    pub fn init_with<F>(shape: HexWorldShape, f: F) -> Self
+    where
+        F: FnOnce() -> T,
+    {
+        Self::init_with_index(MapIndex::new(shape), f)
+    }
 }
 
-impl<T: ?Sized + Copy> StaticMap<T> {
-    pub fn new(shape: HexWorldShape) -> Self {
-        let indexer = MapIndex::new(shape);
+impl<T, I: Indexer> StaticMap<T, I> {
+    pub fn with_index(indexer: I) -> Self {
         let mut vec = Vec::new();
-        vec.resize(indexer.capacity(), CellBucket::Empty);
+        vec.resize_with(indexer.capacity(), || CellBucket::Empty);
 
         Self {
             index: indexer,
             store: vec,
+            len: 0,
         }
     }
 
+    // Wrapping shapes accept coordinates past the seam, the cell is stored under its canonical coords
     pub fn set(&mut self, coords: HexCoord, value: Option<T>) -> Option<T> {
         let index = self.index.index(coords);
-        self.set_internal(index, coords, value)
+        self.set_internal(index, self.index.coords(index), value)
     }
 
     pub fn set_index(&mut self, index: usize, value: Option<T>) -> Option<T> {
-        let coords = self.index.coord(index);
+        let coords = self.index.coords(index);
         self.set_internal(index, coords, value)
     }
 
-    pub fn init_with<F>(shape: HexWorldShape, f: F) -> Self
-    where
-        F: FnOnce() -> T,
-    {
-        let mut r = Self::new(shape);
-
-        let v = f();
+    fn set_internal(&mut self, index: usize, coords: HexCoord, value: Option<T>) -> Option<T> {
+        let bucket = match value {
+            Some(v) => CellBucket::Occupied(coords, v),
+            None => CellBucket::Empty,
+        };
 
-        for i in 0..r.index.capacity() {
-            r.set_index(i, Some(v));
+        if matches!(bucket, CellBucket::Occupied(_, _)) {
+            self.len += 1;
+        }
+        match mem::replace(&mut self.store[index], bucket) {
+            CellBucket::Occupied(_, v) => {
+                self.len -= 1;
+                Some(v)
+            }
+            CellBucket::Empty => None,
         }
+    }
 
-        r
+    // Panics if the cell is off the map
+    #[inline]
+    pub fn insert(&mut self, coords: HexCoord, value: T) -> Option<T> {
+        self.set(coords, Some(value))
     }
 
-    fn set_internal(&mut self, index: usize, coords: HexCoord, value: Option<T>) -> Option<T> {
-        let prev = *self.store.get(index).unwrap();
+    // Cells off the map never hold a value
+    pub fn remove(&mut self, coords: HexCoord) -> Option<T> {
+        let index = self.index.try_index(coords)?;
+        self.set_internal(index, coords, None)
+    }
 
-        self.store[index] = match value {
-            Some(v) => CellBucket::Occupied(coords, v),
-            None => CellBucket::Empty,
-        };
+    // Puts a value in the cell and returns the one it held, like Option::replace. Same as insert,
+    // panics if the cell is off the map.
+    #[inline]
+    pub fn replace(&mut self, coords: HexCoord, value: T) -> Option<T> {
+        self.insert(coords, value)
+    }
+
+    // Moves the value out of the cell and leaves it empty, like Option::take. Same as remove, cells
+    // off the map give None.
+    #[inline]
+    pub fn take(&mut self, coords: HexCoord) -> Option<T> {
+        self.remove(coords)
+    }
+
+    // Panics if the cell is off the map
+    pub fn entry(&mut self, coords: HexCoord) -> Entry<'_, T> {
+        let index = self.index.index(coords);
+        let coords = self.index.coords(index);
+        let bucket = &mut self.store[index];
+        let len = &mut self.len;
+        match bucket {
+            CellBucket::Occupied(_, _) => Entry::Occupied(OccupiedEntry::new(coords, bucket, len)),
+            CellBucket::Empty => Entry::Vacant(VacantEntry::new(coords, bucket, len)),
+        }
+    }
+
+    pub(crate) fn bucket(&self, coords: HexCoord) -> Option<&CellBucket<T>> {
+        self.store.get(self.index.try_index(coords)?)
+    }
+
+    pub fn get(&self, coords: HexCoord) -> Option<&T> {
+        match self.bucket(coords)? {
+            CellBucket::Occupied(_, v) => Some(v),
+            CellBucket::Empty => None,
+        }
+    }
 
-        match prev {
+    pub fn get_mut(&mut self, coords: HexCoord) -> Option<&mut T> {
+        let index = self.index.try_index(coords)?;
+        match &mut self.store[index] {
             CellBucket::Occupied(_, v) => Some(v),
             CellBucket::Empty => None,
         }
     }
 
+    // Number of occupied cells
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Whether the cell holds a value, `index().contains` tells whether it is on the map
+    #[inline]
+    pub fn is_occupied(&self, coords: HexCoord) -> bool {
+        self.get(coords).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.store.iter_mut().for_each(|b| *b = CellBucket::Empty);
+        self.len = 0;
+    }
+
+    // Empties every cell for which `keep` returns false
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(HexCoord, &mut T) -> bool,
+    {
+        for bucket in self.store.iter_mut() {
+            if let CellBucket::Occupied(c, v) = bucket {
+                if !keep(*c, v) {
+                    *bucket = CellBucket::Empty;
+                    self.len -= 1;
+                }
+            }
+        }
+    }
+
     pub fn footprint(&self) -> usize {
-        mem::size_of::<CellBucket<T>>() * &self.store.len() + mem::size_of::<MapIndex>()
+        mem::size_of::<CellBucket<T>>() * self.store.len() + mem::size_of::<I>()
     }
 
     #[inline]
-    pub fn index(&self) -> MapIndex {
-        self.index
+    pub fn index(&self) -> &I {
+        &self.index
     }
 
     pub fn iter(&self) -> Iter<'_, CellBucket<T>> {
@@ -106,56 +206,160 @@ impl<T: ?Sized + Copy> StaticMap<T> {
     pub fn coord_iter(&self) -> impl Iterator<Item = (HexCoord, Option<&T>)> {
        self.store.iter().enumerate().map(|(i, b)| match b {
             CellBucket::Occupied(c, t) => (*c, Some(t)),
-            CellBucket::Empty => (self.index.coord(i), None),
+            CellBucket::Empty => (self.index.coords(i), None),
+        })
+    }
+
+    // Occupied cells in index order
+    pub fn occupied(&self) -> impl Iterator<Item = (HexCoord, &T)> {
+        self.store.iter().filter_map(|b| match b {
+            CellBucket::Occupied(c, v) => Some((*c, v)),
+            CellBucket::Empty => None,
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (HexCoord, &mut T)> {
+        self.store.iter_mut().filter_map(|b| match b {
+            CellBucket::Occupied(c, v) => Some((*c, v)),
+            CellBucket::Empty => None,
         })
     }
 
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.occupied().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<T: Clone, I: Indexer> StaticMap<T, I> {
+    pub fn init_with_index<F>(indexer: I, f: F) -> Self
+    where
+        F: FnOnce() -> T,
+    {
+        let mut r = Self::with_index(indexer);
+
+        let v = f();
+
+        for i in 0..r.index.capacity() {
+            r.set_index(i, Some(v.clone()));
+        }
+
+        r
+    }
+
+    // On wrapping shapes neighbours across the seam are reported by their canonical coords, cells off
+    // the map have no value
     pub fn neighbourhood(&self, coords: HexCoord) -> Neighbourhood<T> {
-        let t = self.get_segment(coords.neighbour(Edge::QS));
-        let top = [
-            NeighbourhoodNode::from_bucket(coords.neighbour(Edge::QS), t[0]),
-            NeighbourhoodNode::from_bucket(coords.neighbour(Edge::Q), t[1]),
-        ];
-
-        let m = self.get_segment(coords.neighbour(Edge::S));
-        let middle = [
-            NeighbourhoodNode::from_bucket(coords.neighbour(Edge::S), m[0]),
-            NeighbourhoodNode::from_bucket(coords, m[1]),
-            NeighbourhoodNode::from_bucket(coords.neighbour(Edge::QR), m[2]),
-        ];
-
-        let b = self.get_segment(coords.neighbour(Edge::RS));
-        let bottom = [
-            NeighbourhoodNode::from_bucket(coords.neighbour(Edge::RS), b[0]),
-            NeighbourhoodNode::from_bucket(coords.neighbour(Edge::R), b[1]),
-        ];
-
-        Neighbourhood::new(top, middle, bottom)
-    }
-
-    fn get_segment(&self, coords: HexCoord) -> [CellBucket<T>; 3] {
-        let i = self.index().index(coords);
+        let t = self.row(coords.neighbour(Edge::QS));
+        let m = self.row(coords.neighbour(Edge::S));
+        let b = self.row(coords.neighbour(Edge::RS));
+
+        let [t0, t1, _] = t;
+        let [b0, b1, _] = b;
+        Neighbourhood::new([t0, t1], m, [b0, b1])
+    }
+
+    // Three cells running along QR
+    fn row(&self, start: HexCoord) -> [NeighbourhoodNode<T>; 3] {
+        let cells = [start, start.neighbour(Edge::QR), start.neighbour(Edge::QR).neighbour(Edge::QR)]
+            .map(|c| self.index.canonical(c));
+        match self.get_segment(start) {
+            Some(s) => [0, 1, 2].map(|i| NeighbourhoodNode::from_bucket(cells[i], s[i])),
+            None => cells.map(|c| NeighbourhoodNode::new(c, self.get(c).cloned())),
+        }
+    }
+
+    // Cells outside the map are reported with no value, on wrapping shapes every cell is reported by
+    // its canonical coords like `neighbourhood`
+    pub fn extended_neighbourhood(&self, coords: HexCoord) -> ExtendedNeighbourhood<T> {
+        let node = |c: HexCoord| {
+            let c = self.index.canonical(c);
+            NeighbourhoodNode::new(c, self.get(c).cloned())
+        };
+        ExtendedNeighbourhood::new(
+            node(coords),
+            Edge::ALL.map(|e| node(coords.neighbour(e))),
+            Diagonal::ALL.map(|d| node(coords.diagonal_neighbour(d))),
+        )
+    }
+
+    // Hexmod stores cells running along QR next to each other, wrapping round at the end of the
+    // store, so a row can be read in one go. Only when the row is entirely on the map and three
+    // distinct cells long, on tiny wrapping maps it runs into itself.
+    fn get_segment(&self, coords: HexCoord) -> Option<[&CellBucket<T>; 3]> {
+        if !self.index.contiguous_qr_rows() || self.index.capacity() < 3 {
+            return None;
+        }
+        let i = self.index.try_index(coords)?;
         let end = coords.neighbour(Edge::QR).neighbour(Edge::QR);
-        let j = self.index().index(end);
-        let d = self.index().capacity() - i;
+        let j = self.index.try_index(end)?;
+        let d = self.index.capacity() - i;
         match d {
             1 => {
                 let s = &self.store[0..=j];
-                [self.store[i], s[0], s[1]]
+                Some([&self.store[i], &s[0], &s[1]])
             }
             2 => {
                 let s = &self.store[i..];
-                [s[0], s[1], self.store[j]]
+                Some([&s[0], &s[1], &self.store[j]])
             }
             _ => {
-                let mut o = [CellBucket::default(); 3];
-                o.copy_from_slice(&self.store[i..=j]);
-                o
+                let s = &self.store[i..=j];
+                Some([&s[0], &s[1], &s[2]])
             }
         }
     }
 }
 
+// Panics if the cell is off the map or empty
+impl<T, I: Indexer> Index<HexCoord> for StaticMap<T, I> {
+    type Output = T;
+
+    fn index(&self, coords: HexCoord) -> &T {
+        self.get(coords).unwrap_or_else(|| panic!("No value at {coords:?}"))
+    }
+}
+
+impl<T, I: Indexer> IndexMut<HexCoord> for StaticMap<T, I> {
+    fn index_mut(&mut self, coords: HexCoord) -> &mut T {
+        self.get_mut(coords).unwrap_or_else(|| panic!("No value at {coords:?}"))
+    }
+}
+
+// Panics if any cell is off the map
+impl<T, I: Indexer> Extend<(HexCoord, T)> for StaticMap<T, I> {
+    fn extend<It: IntoIterator<Item = (HexCoord, T)>>(&mut self, iter: It) {
+        for (coords, value) in iter {
+            self.insert(coords, value);
+        }
+    }
+}
+
+impl<T> StaticMap<T, CustomIndexer> {
+    // There is no shape to start from, so the map is given a mask of exactly the collected cells.
+    // Later values for the same cell replace earlier ones.
+    pub fn from_iter_with<It>(iter: It, orientation: HexOrientation) -> Self
+    where
+        It: IntoIterator<Item = (HexCoord, T)>,
+    {
+        let values: Vec<_> = iter.into_iter().collect();
+        let mask = HexMask::new(values.iter().map(|(c, _)| *c));
+        let mut map = Self::with_index(CustomIndexer::new(mask, orientation));
+        map.extend(values);
+        map
+    }
+}
+
+// Collected maps are flat, use `from_iter_with` for pointy ones
+impl<T> FromIterator<(HexCoord, T)> for StaticMap<T, CustomIndexer> {
+    fn from_iter<It: IntoIterator<Item = (HexCoord, T)>>(iter: It) -> Self {
+        Self::from_iter_with(iter, HexOrientation::Flat)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{StaticMap, HexCoord, HexOrientation, HexWorldShape};
@@ -164,7 +368,7 @@ mod test {
     fn get_segment_start_is_index_capacity() {
         let map: StaticMap<bool> = StaticMap::init_with(HexWorldShape::Hexagon(5, HexOrientation::Flat), || true);
 
-        let s = map.get_segment(HexCoord::from_axial(-1, 1));
+        let s = map.get_segment(HexCoord::from_axial(-1, 1)).unwrap();
 
         assert_eq!(*s[0].unwrap().0, HexCoord::from_axial(-1, 1));
         assert_eq!(*s[1].unwrap().0, HexCoord::from_axial(0, 0));
@@ -175,7 +379,7 @@ mod test {
     fn get_segment_start_is_one_less_than_index_capacity() {
         let map: StaticMap<bool> = StaticMap::init_with(HexWorldShape::Hexagon(5, HexOrientation::Flat), || true);
 
-        let s = map.get_segment(HexCoord::from_axial(-2, 2));
+        let s = map.get_segment(HexCoord::from_axial(-2, 2)).unwrap();
 
         assert_eq!(*s[0].unwrap().0, HexCoord::from_axial(-2, 2));
         assert_eq!(*s[1].unwrap().0, HexCoord::from_axial(-1, 1));
@@ -186,10 +390,18 @@ mod test {
     fn get_segment_start_coord_0_0() {
         let map: StaticMap<bool> = StaticMap::init_with(HexWorldShape::Hexagon(5, HexOrientation::Flat), || true);
 
-        let s = map.get_segment(HexCoord::from_axial(0, 0));
+        let s = map.get_segment(HexCoord::from_axial(0, 0)).unwrap();
 
         assert_eq!(*s[0].unwrap().0, HexCoord::from_axial(0, 0));
         assert_eq!(*s[1].unwrap().0, HexCoord::from_axial(1, -1));
         assert_eq!(*s[2].unwrap().0, HexCoord::from_axial(2, -2));
     }
+
+    #[test]
+    fn get_segment_off_map_is_none() {
+        let map: StaticMap<bool> = StaticMap::init_with(HexWorldShape::Hexagon(5, HexOrientation::Flat), || true);
+
+        assert!(map.get_segment(HexCoord::from_axial(3, -3)).is_none());
+        assert!(map.get_segment(HexCoord::from_axial(-6, 1)).is_none());
+    }
 }
@@ -1,6 +1,11 @@
+mod element_map;
+mod entry;
 mod map;
 mod neighbourhood;
 #[cfg(test)]
 mod tests;
 
+pub use element_map::{EdgeMap, ElementMap, MapElement, VertexMap};
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
 pub use map::{CellBucket, StaticMap};
+pub use neighbourhood::{ExtendedNeighbourhood, Neighbourhood, NeighbourhoodNode};
@@ -1,6 +1,6 @@
 use std::{ops::Index, slice::Iter};
 
-use crate::{CellBucket, HexCoord};
+use crate::{CellBucket, Diagonal, Edge, HexCoord};
 
 #[derive(Copy, Clone)]
 pub struct NeighbourhoodNode<T> {
@@ -8,16 +8,16 @@ pub struct NeighbourhoodNode<T> {
     pub value: Option<T>,
 }
 
-impl<T: ?Sized + Copy> NeighbourhoodNode<T> {
+impl<T: Clone> NeighbourhoodNode<T> {
     pub fn new(coords: HexCoord, value: Option<T>) -> Self {
         Self { coords, value }
     }
 
-    pub fn from_bucket(coords: HexCoord, bucket: CellBucket<T>) -> Self {
+    pub fn from_bucket(coords: HexCoord, bucket: &CellBucket<T>) -> Self {
         Self {
             coords,
             value: match bucket {
-                CellBucket::Occupied(_, v) => Some(v),
+                CellBucket::Occupied(_, v) => Some(v.clone()),
                 CellBucket::Empty => None,
             },
         }
@@ -28,22 +28,23 @@ pub struct Neighbourhood<T> {
     arr: [NeighbourhoodNode<T>; 7],
 }
 
-impl<T: ?Sized + Copy> Neighbourhood<T> {
+impl<T: Clone> Neighbourhood<T> {
     pub(crate) fn new(
         top: [NeighbourhoodNode<T>; 2],
         middle: [NeighbourhoodNode<T>; 3],
         bottom: [NeighbourhoodNode<T>; 2],
     ) -> Self {
+        let [t0, t1] = top;
+        let [m0, m1, m2] = middle;
+        let [b0, b1] = bottom;
         Self {
-            arr: [
-                top[0], top[1], middle[0], middle[1], middle[2], bottom[0], bottom[1],
-            ],
+            arr: [t0, t1, m0, m1, m2, b0, b1],
         }
     }
 
     #[inline]
     pub fn center(&self) -> NeighbourhoodNode<T> {
-        self.arr[3]
+        self.arr[3].clone()
     }
 
     pub fn iter(&self) -> Iter<'_, NeighbourhoodNode<T>> {
@@ -59,3 +60,44 @@ impl<T> Index<usize> for Neighbourhood<T> {
         &self.arr[index]
     }
 }
+
+// The six edge neighbours and the six diagonal neighbours around a center cell
+pub struct ExtendedNeighbourhood<T> {
+    center: NeighbourhoodNode<T>,
+    edges: [NeighbourhoodNode<T>; 6],
+    diagonals: [NeighbourhoodNode<T>; 6],
+}
+
+impl<T: Clone> ExtendedNeighbourhood<T> {
+    pub(crate) fn new(
+        center: NeighbourhoodNode<T>,
+        edges: [NeighbourhoodNode<T>; 6],
+        diagonals: [NeighbourhoodNode<T>; 6],
+    ) -> Self {
+        Self {
+            center,
+            edges,
+            diagonals,
+        }
+    }
+
+    #[inline]
+    pub fn center(&self) -> NeighbourhoodNode<T> {
+        self.center.clone()
+    }
+
+    #[inline]
+    pub fn edge(&self, edge: Edge) -> NeighbourhoodNode<T> {
+        self.edges[edge.index()].clone()
+    }
+
+    #[inline]
+    pub fn diagonal(&self, diagonal: Diagonal) -> NeighbourhoodNode<T> {
+        self.diagonals[diagonal.index()].clone()
+    }
+
+    // Edge neighbours in Edge order followed by diagonal neighbours in Diagonal order
+    pub fn iter(&self) -> impl Iterator<Item = &NeighbourhoodNode<T>> {
+        self.edges.iter().chain(self.diagonals.iter())
+    }
+}
@@ -1,9 +1,13 @@
-use crate::{Edge, FlatMap, HexCoord, HexCoordinate, HexOrientation, HexWorldShape};
+use crate::{
+    Diagonal, Edge, HexCoord, HexMask, HexCoordinate, HexOrientation, HexWorldShape, ParallelogramAxis, StaticMap,
+    find_path, CellBucket, CustomIndexer, HexagonIndexer, OffsetCoord, OffsetParity, Indexer, TrianglePointing,
+};
+use test_case::test_case;
 
 // Neighbourhoods
 #[test]
 pub fn neighbours_returns_correct_coords() {
-    let map: FlatMap<bool> = FlatMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+    let map: StaticMap<bool> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
     let center = HexCoord::from_axial(0, 0);
 
     let neighbourhood = map.neighbourhood(center);
@@ -22,7 +26,7 @@ pub fn neighbours_returns_correct_coords() {
 
 #[test]
 pub fn neighbours_on_map_edge_returns_correct_coords() {
-    let map: FlatMap<bool> = FlatMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+    let map: StaticMap<bool> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
     let center = HexCoord::from_axial(1, -1);
 
     let neighbourhood = map.neighbourhood(center);
@@ -41,7 +45,7 @@ pub fn neighbours_on_map_edge_returns_correct_coords() {
 
 #[test]
 pub fn neighbourhood_with_values() {
-    let mut map: FlatMap<bool> = FlatMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+    let mut map: StaticMap<bool> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
     let center = HexCoord::from_axial(-1, 0);
 
     map.set(center.neighbour(Edge::RS), Some(true));
@@ -51,3 +55,383 @@ pub fn neighbourhood_with_values() {
     assert_eq!(neighbourhood.center().value, None);
     assert_eq!(neighbourhood[5].value, Some(true));
 }
+
+#[test]
+pub fn extended_neighbourhood_returns_correct_coords() {
+    let map: StaticMap<bool> = StaticMap::new(HexWorldShape::Hexagon(4, HexOrientation::Flat));
+    let center = HexCoord::from_axial(1, -1);
+
+    let neighbourhood = map.extended_neighbourhood(center);
+
+    assert_eq!(neighbourhood.center().coords, center);
+    for e in Edge::ALL {
+        assert_eq!(neighbourhood.edge(e).coords, center.neighbour(e));
+    }
+    for d in Diagonal::ALL {
+        assert_eq!(neighbourhood.diagonal(d).coords, center.diagonal_neighbour(d));
+    }
+    assert_eq!(neighbourhood.iter().count(), 12);
+}
+
+#[test]
+pub fn extended_neighbourhood_with_values() {
+    let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+    let center = HexCoord::from_axial(0, 0);
+
+    map.set(center, Some(1));
+    map.set(center.neighbour(Edge::R), Some(2));
+    map.set(center.diagonal_neighbour(Diagonal::QSQ), Some(3));
+
+    let neighbourhood = map.extended_neighbourhood(center);
+
+    assert_eq!(neighbourhood.center().value, Some(1));
+    assert_eq!(neighbourhood.edge(Edge::R).value, Some(2));
+    assert_eq!(neighbourhood.diagonal(Diagonal::QSQ).value, Some(3));
+    assert_eq!(neighbourhood.iter().filter(|n| n.value.is_some()).count(), 2);
+}
+
+#[test]
+pub fn extended_neighbourhood_outside_map_has_no_value() {
+    let map: StaticMap<bool> = StaticMap::init_with(HexWorldShape::Hexagon(2, HexOrientation::Flat), || true);
+
+    let neighbourhood = map.extended_neighbourhood(HexCoord::from_axial(0, 0));
+
+    assert!(Edge::ALL.iter().all(|e| neighbourhood.edge(*e).value == Some(true)));
+    assert!(Diagonal::ALL.iter().all(|d| neighbourhood.diagonal(*d).value.is_none()));
+}
+
+#[test]
+pub fn wrapping_neighbourhood_crosses_seam() {
+    let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::WrappingHexagon(3, HexOrientation::Flat));
+    let center = HexCoord::from_axial(2, -1);
+    let across = map.index().neighbour(center, Edge::QR);
+    map.set(across, Some(5));
+
+    let neighbourhood = map.neighbourhood(center);
+
+    assert_eq!(neighbourhood[4].coords, across);
+    assert_eq!(neighbourhood[4].value, Some(5));
+    assert!(neighbourhood.iter().all(|n| map.index().canonical(n.coords) == n.coords));
+}
+
+#[test]
+pub fn set_past_seam_stores_canonical_coords() {
+    let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::WrappingHexagon(3, HexOrientation::Flat));
+    let past = HexCoord::new(3, 0, -3);
+    let canonical = map.index().canonical(past);
+    assert_ne!(past, canonical);
+
+    map.set(past, Some(1));
+
+    assert_eq!(map.occupied().collect::<Vec<_>>(), vec![(canonical, &1)]);
+    assert_eq!(map.iter_mut().map(|(c, _)| c).collect::<Vec<_>>(), vec![canonical]);
+    assert_eq!(map.entry(past).coords(), canonical);
+    assert_eq!(map.get(past), Some(&1));
+}
+
+#[test]
+pub fn wrapping_extended_neighbourhood_is_canonical() {
+    let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::WrappingHexagon(3, HexOrientation::Flat));
+    let center = HexCoord::from_axial(2, -1);
+    let across = map.index().canonical(center.diagonal_neighbour(Diagonal::QQR));
+    map.set(across, Some(7));
+
+    let neighbourhood = map.extended_neighbourhood(center);
+
+    assert_eq!(neighbourhood.diagonal(Diagonal::QQR).coords, across);
+    assert_eq!(neighbourhood.diagonal(Diagonal::QQR).value, Some(7));
+    assert!(neighbourhood.iter().all(|n| map.index().contains(n.coords)));
+    assert!(neighbourhood.iter().all(|n| map.index().canonical(n.coords) == n.coords));
+}
+
+#[test]
+pub fn one_cell_wrapping_map_is_its_own_neighbourhood() {
+    let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::WrappingHexagon(1, HexOrientation::Flat));
+    let cell = HexCoord::from_axial(0, 0);
+    map.set(cell, Some(3));
+
+    let neighbourhood = map.neighbourhood(cell);
+
+    assert!(neighbourhood.iter().all(|n| n.coords == cell && n.value == Some(3)));
+}
+
+// Shapes
+#[test_case(HexWorldShape::Parallelogram(5, 3, ParallelogramAxis::QR, HexOrientation::Flat))]
+#[test_case(HexWorldShape::Parallelogram(4, 4, ParallelogramAxis::SQ, HexOrientation::Pointy))]
+#[test_case(HexWorldShape::Triangle(5, TrianglePointing::Left))]
+#[test_case(HexWorldShape::Triangle(5, TrianglePointing::Up))]
+pub fn every_cell_of_shape_is_stored(shape: HexWorldShape) {
+    let mut map: StaticMap<usize> = StaticMap::new(shape);
+    let index = *map.index();
+    for i in 0..index.capacity() {
+        assert_eq!(map.set(index.coord(i), Some(i)), None);
+    }
+
+    for (coord, value) in map.coord_iter() {
+        assert_eq!(index.index(coord), *value.unwrap());
+    }
+    assert!(map.get(index.center()).is_some());
+}
+
+#[test]
+pub fn custom_shape_stores_one_slot_per_cell() {
+    let cells = [HexCoord::from_axial(0, 0), HexCoord::from_axial(5, -2), HexCoord::from_axial(-7, 9)];
+    let mut map = StaticMap::with_index(CustomIndexer::new(HexMask::new(cells), HexOrientation::Flat));
+
+    assert_eq!(map.iter().count(), 3);
+    assert_eq!(map.index().try_index(HexCoord::from_axial(1, 0)), None);
+    map.set(cells[2], Some(1u8));
+    assert_eq!(map.get(cells[2]), Some(&1));
+    assert_eq!(map.get(HexCoord::from_axial(-6, 9)), None);
+}
+
+#[test_case(HexCoord::from_axial(0, 0))]
+#[test_case(HexCoord::from_axial(2, -1))]
+#[test_case(HexCoord::from_axial(-3, 3))]
+pub fn pointy_hexagon_neighbourhood(center: HexCoord) {
+    let mut map: StaticMap<i32> = StaticMap::new(HexWorldShape::Hexagon(4, HexOrientation::Pointy));
+    for i in 0..map.index().capacity() {
+        map.set_index(i, Some(i as i32));
+    }
+
+    let neighbourhood = map.neighbourhood(center);
+
+    assert_eq!(neighbourhood.center().coords, center);
+    assert_eq!(neighbourhood.center().value, Some(map.index().index(center) as i32));
+    assert_eq!(neighbourhood[6].coords, center.neighbour(Edge::R));
+}
+
+// Bounds
+#[test]
+pub fn neighbourhood_off_map_has_no_value() {
+    let map: StaticMap<bool> = StaticMap::init_with(HexWorldShape::Hexagon(3, HexOrientation::Flat), || true);
+    let center = HexCoord::from_axial(2, -2);
+
+    let neighbourhood = map.neighbourhood(center);
+
+    let on_map = neighbourhood.iter().filter(|n| n.value.is_some()).count();
+    assert_eq!(on_map, 4);
+    assert!(neighbourhood.iter().all(|n| n.value.is_some() == map.index().contains(n.coords)));
+}
+
+#[test]
+#[should_panic]
+pub fn set_off_map_panics() {
+    let mut map: StaticMap<u8> = StaticMap::new(HexWorldShape::Rectangle(3, 3, HexOrientation::Flat));
+    map.set(map.index().offset_coord(0, 3), Some(1));
+}
+
+// Custom indexers
+
+// A single row of cells along q
+struct StripIndexer {
+    len: usize,
+}
+
+impl Indexer for StripIndexer {
+    fn index(&self, coord: HexCoord) -> usize {
+        self.try_index(coord).unwrap()
+    }
+
+    fn try_index(&self, coord: HexCoord) -> Option<usize> {
+        (coord.r() == 0 && (0..self.len as i32).contains(&coord.q())).then_some(coord.q() as usize)
+    }
+
+    fn capacity(&self) -> usize {
+        self.len
+    }
+
+    fn coords(&self, index: usize) -> HexCoord {
+        HexCoord::from_axial(index as i32, 0)
+    }
+
+    fn offset_coord(&self, col: i32, _row: i32) -> HexCoord {
+        HexCoord::from_axial(col, 0)
+    }
+}
+
+#[test]
+pub fn static_map_with_custom_indexer() {
+    let mut map = StaticMap::init_with_index(StripIndexer { len: 5 }, || 1u8);
+    map.set(HexCoord::from_axial(2, 0), Some(7));
+
+    let neighbourhood = map.neighbourhood(HexCoord::from_axial(2, 0));
+    assert_eq!(neighbourhood.center().value, Some(7));
+    assert_eq!(neighbourhood.iter().filter(|n| n.value.is_some()).count(), 3);
+
+    assert_eq!(map.coord_iter().count(), 5);
+    assert_eq!(map.footprint(), 5 * std::mem::size_of::<CellBucket<u8>>() + std::mem::size_of::<StripIndexer>());
+
+    let (path, cost) = find_path(&map, HexCoord::from_axial(0, 0), HexCoord::from_axial(4, 0), |_, v| v.map(|_| 1)).unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!(cost, 4);
+}
+
+#[test]
+pub fn concrete_indexer_matches_map_index() {
+    let shape = HexWorldShape::Hexagon(4, HexOrientation::Pointy);
+    let mut dynamic: StaticMap<usize> = StaticMap::new(shape);
+    let mut concrete = StaticMap::with_index(HexagonIndexer::new(4, HexOrientation::Pointy));
+    for i in 0..dynamic.index().capacity() {
+        dynamic.set_index(i, Some(i));
+        concrete.set_index(i, Some(i));
+    }
+
+    for coord in [HexCoord::from_axial(0, 0), HexCoord::from_axial(3, -3), HexCoord::from_axial(-1, 2)] {
+        let a = dynamic.neighbourhood(coord);
+        let b = concrete.neighbourhood(coord);
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| x.coords == y.coords && x.value == y.value));
+    }
+}
+
+// Non-Copy values
+#[test]
+pub fn stores_owned_values() {
+    let mut map: StaticMap<String> = StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+    let a = HexCoord::from_axial(1, 0);
+
+    assert_eq!(map.insert(a, "town".to_string()), None);
+    map.get_mut(a).unwrap().push_str("ship");
+    assert_eq!(map.get(a).map(String::as_str), Some("township"));
+
+    assert_eq!(map.insert(a, "city".to_string()).as_deref(), Some("township"));
+    assert_eq!(map.replace(a, "capital".to_string()).as_deref(), Some("city"));
+    assert_eq!(map.set(a, None).as_deref(), Some("capital"));
+    assert_eq!(map.replace(a, "hamlet".to_string()), None);
+    assert_eq!(map.get(a).map(String::as_str), Some("hamlet"));
+}
+
+#[test]
+pub fn take_and_remove_leave_cell_empty() {
+    let mut map: StaticMap<Vec<u32>> = StaticMap::init_with(HexWorldShape::Hexagon(2, HexOrientation::Flat), || vec![1, 2]);
+    let a = HexCoord::from_axial(0, 1);
+
+    assert_eq!(map.take(a), Some(vec![1, 2]));
+    assert_eq!(map.take(a), None);
+    assert_eq!(map.take(HexCoord::from_axial(9, 0)), None);
+    assert_eq!(map.remove(HexCoord::from_axial(0, 0)), Some(vec![1, 2]));
+    assert_eq!(map.remove(HexCoord::from_axial(9, 0)), None);
+    assert_eq!(map.coord_iter().filter(|(_, v)| v.is_some()).count(), 5);
+}
+
+#[test]
+pub fn neighbourhood_clones_values() {
+    let mut map: StaticMap<Box<str>> = StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+    let center = HexCoord::from_axial(0, 0);
+    map.insert(center.neighbour(Edge::R), "east".into());
+
+    let neighbourhood = map.neighbourhood(center);
+    assert_eq!(neighbourhood[6].value.as_deref(), Some("east"));
+    assert_eq!(map.extended_neighbourhood(center).edge(Edge::R).value.as_deref(), Some("east"));
+}
+
+#[test]
+#[should_panic]
+pub fn insert_off_map_panics() {
+    let mut map: StaticMap<String> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+    map.insert(HexCoord::from_axial(5, 0), String::new());
+}
+
+// Collection API
+#[test]
+pub fn len_counts_occupied_cells() {
+    let mut map: StaticMap<u32> = StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+    assert!(map.is_empty());
+
+    map.insert(HexCoord::from_axial(0, 0), 1);
+    map.insert(HexCoord::from_axial(1, 0), 2);
+    map.insert(HexCoord::from_axial(1, 0), 3);
+    *map.entry(HexCoord::from_axial(0, 2)).or_default() += 4;
+    assert_eq!(map.len(), 3);
+
+    map.remove(HexCoord::from_axial(0, 0));
+    map.set(HexCoord::from_axial(2, 0), None);
+    assert_eq!(map.len(), 2);
+    assert!(map.is_occupied(HexCoord::from_axial(1, 0)));
+    assert!(!map.is_occupied(HexCoord::from_axial(0, 0)));
+    assert!(map.index().contains(HexCoord::from_axial(0, 0)));
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.occupied().count(), 0);
+}
+
+#[test]
+pub fn iterators_skip_empty_cells() {
+    let mut map: StaticMap<u32> = StaticMap::new(HexWorldShape::Hexagon(3, HexOrientation::Flat));
+    let cells = [HexCoord::from_axial(-1, 0), HexCoord::from_axial(2, -1), HexCoord::from_axial(0, 2)];
+    for (i, c) in cells.iter().enumerate() {
+        map.insert(*c, i as u32);
+    }
+
+    for (_, v) in map.iter_mut() {
+        *v *= 10;
+    }
+    map.values_mut().for_each(|v| *v += 1);
+
+    let mut values: Vec<u32> = map.values().copied().collect();
+    values.sort();
+    assert_eq!(values, vec![1, 11, 21]);
+    assert!(map.occupied().all(|(c, v)| cells.iter().position(|x| *x == c) == Some((*v / 10) as usize)));
+}
+
+#[test]
+pub fn retain_drops_rejected_cells() {
+    let mut map: StaticMap<i32> = StaticMap::new(HexWorldShape::Rectangle(4, 3, HexOrientation::Pointy));
+    let coords: Vec<HexCoord> = map.coord_iter().map(|(c, _)| c).collect();
+    map.extend(coords.iter().map(|c| (*c, c.q())));
+
+    map.retain(|_, v| {
+        *v += 1;
+        *v > 1
+    });
+
+    assert_eq!(map.len(), map.values().count());
+    assert!(map.occupied().all(|(c, v)| *v == c.q() + 1 && c.q() > 0));
+}
+
+#[test]
+pub fn index_reads_and_writes_cells() {
+    let mut map: StaticMap<String> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+    let a = HexCoord::from_axial(1, -1);
+    map.insert(a, "hill".to_string());
+
+    map[a].push_str("top");
+    assert_eq!(map[a], "hilltop");
+}
+
+#[test]
+#[should_panic]
+pub fn index_empty_cell_panics() {
+    let map: StaticMap<u8> = StaticMap::new(HexWorldShape::Hexagon(2, HexOrientation::Flat));
+    let _ = map[HexCoord::from_axial(0, 0)];
+}
+
+#[test]
+pub fn collect_shapes_map_to_cells() {
+    let a = HexCoord::from_axial(4, -9);
+    let b = HexCoord::from_axial(-3, 2);
+    let map: StaticMap<&str, CustomIndexer> = [(a, "fort"), (b, "well"), (a, "ruin")].into_iter().collect();
+
+    assert_eq!(map.index().capacity(), 2);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[a], "ruin");
+    assert_eq!(map[b], "well");
+    assert!(!map.index().contains(HexCoord::from_axial(0, 0)));
+
+    let empty: StaticMap<u8, CustomIndexer> = std::iter::empty().collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+pub fn from_iter_with_keeps_orientation() {
+    let cells = [HexCoord::from_axial(0, 0), HexCoord::from_axial(1, 0), HexCoord::from_axial(0, 1)];
+    let pointy = StaticMap::from_iter_with(cells.map(|c| (c, ())), HexOrientation::Pointy);
+    let flat: StaticMap<(), CustomIndexer> = cells.map(|c| (c, ())).into_iter().collect();
+
+    let offset = OffsetCoord::new(1, 2, OffsetParity::Odd);
+    assert_eq!(pointy.index().offset_coord(1, 2), offset.to_hex(HexOrientation::Pointy));
+    assert_eq!(flat.index().offset_coord(1, 2), offset.to_hex(HexOrientation::Flat));
+    assert_ne!(pointy.index().offset_coord(1, 2), flat.index().offset_coord(1, 2));
+    assert_eq!(pointy.len(), 3);
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use crate::{CellBucket, FracHexCoord, HexCoord, Indexer, StaticMap};
+
+// Lines are traced twice, nudged to either side, so a line running exactly along an edge can see
+// past a single blocker on one side. Tracing b -> a visits the same cells as a -> b which keeps
+// the result symmetric. The nudge is applied to the offset from a, not absolute positions, so this
+// holds anywhere on the map.
+const NUDGES: [FracHexCoord; 2] = [
+    FracHexCoord::new_unchecked(1e-6, 2e-6, -3e-6),
+    FracHexCoord::new_unchecked(-1e-6, -2e-6, 3e-6),
+];
+
+// Whether a can see b. The end points themselves never block, so walls are visible, but every cell
+// in between must be on the map and not blocking. On wrapping maps the line runs to the nearest
+// image of b.
+pub fn has_line_of_sight<T, I, F>(map: &StaticMap<T, I>, a: HexCoord, b: HexCoord, blocks: F) -> bool
+where
+    I: Indexer,
+    F: Fn(&T) -> bool,
+{
+    let is_clear = |coord: HexCoord| match map.bucket(coord) {
+        Some(CellBucket::Occupied(_, v)) => !blocks(v),
+        Some(CellBucket::Empty) => true,
+        None => false,
+    };
+
+    let b = map.index().nearest_image(a, b);
+    NUDGES.iter().any(|&nudge| {
+        a.line_with_nudge(b, nudge)
+            .filter(|&c| c != a && c != b)
+            .all(|c| is_clear(map.index().canonical(c)))
+    })
+}
+
+// Every cell on the map within `radius` of the origin that has line of sight to it, including the
+// origin. Because it is built on `has_line_of_sight`, a sees b exactly when b sees a. Cells are
+// given by their canonical coords, once each even if several images are in range.
+pub fn field_of_view<T, I, F>(
+    map: &StaticMap<T, I>,
+    origin: HexCoord,
+    radius: usize,
+    blocks: F,
+) -> HashSet<HexCoord>
+where
+    I: Indexer,
+    F: Fn(&T) -> bool,
+{
+    if map.bucket(origin).is_none() {
+        return HashSet::new();
+    }
+
+    let in_range: HashSet<_> = origin
+        .spiral_iter(radius)
+        .map(|c| map.index().canonical(c))
+        .filter(|&c| map.bucket(c).is_some())
+        .collect();
+    in_range
+        .into_iter()
+        .filter(|&c| has_line_of_sight(map, origin, c, &blocks))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CustomIndexer, Edge, HexCoordinate, HexMask, HexOrientation, HexWorldShape};
+    use test_case::test_case;
+
+    fn walled_map(walls: &[HexCoord]) -> StaticMap<bool> {
+        let mut map = StaticMap::init_with(HexWorldShape::Hexagon(8, HexOrientation::Flat), || false);
+        for &w in walls {
+            map.set(w, Some(true));
+        }
+        map
+    }
+
+    #[test]
+    fn open_map_sees_everything_in_radius() {
+        let map = walled_map(&[]);
+        let visible = field_of_view(&map, HexCoord::from_axial(0, 0), 3, |&w| w);
+        assert_eq!(visible.len(), 37);
+    }
+
+    #[test]
+    fn wall_blocks_cells_behind_it() {
+        let origin = HexCoord::from_axial(0, 0);
+        let wall = origin.neighbour(Edge::R);
+        let map = walled_map(&[wall]);
+
+        let visible = field_of_view(&map, origin, 4, |&w| w);
+
+        assert!(visible.contains(&origin));
+        assert!(visible.contains(&wall));
+        assert!(!visible.contains(&HexCoord::from_axial(2, 0)));
+        assert!(!visible.contains(&HexCoord::from_axial(3, 0)));
+        assert!(visible.contains(&origin.neighbour(Edge::QR)));
+    }
+
+    #[test]
+    fn sees_past_single_blocker_along_edge() {
+        // (0,0) -> (2,2) runs between (1,0)/(0,1) and (2,1)/(1,2)
+        let map = walled_map(&[HexCoord::from_axial(1, 0), HexCoord::from_axial(2, 1)]);
+        let a = HexCoord::from_axial(0, 0);
+        let b = HexCoord::from_axial(2, 2);
+        assert!(has_line_of_sight(&map, a, b, |&w| w));
+
+        let map = walled_map(&[HexCoord::from_axial(1, 0), HexCoord::from_axial(0, 1)]);
+        assert!(!has_line_of_sight(&map, a, b, |&w| w));
+    }
+
+    #[test_case(HexCoord::from_axial(300, -200))]
+    #[test_case(HexCoord::from_axial(-5000, 9000))]
+    fn sees_past_single_blocker_far_from_origin(center: HexCoord) {
+        let mut map = StaticMap::with_index(CustomIndexer::new(HexMask::new(center.spiral_iter(4)), HexOrientation::Flat));
+        for c in center.spiral_iter(4) {
+            map.set(c, Some(false));
+        }
+        let a = center;
+        let b = center + HexCoord::from_axial(2, 2);
+
+        for wall in [HexCoord::from_axial(1, 0), HexCoord::from_axial(0, 1)] {
+            map.set(center + wall, Some(true));
+            assert!(has_line_of_sight(&map, a, b, |&w| w));
+            assert!(has_line_of_sight(&map, b, a, |&w| w));
+            map.set(center + wall, Some(false));
+        }
+    }
+
+    #[test]
+    fn off_map_cells_are_not_visible() {
+        let map = walled_map(&[]);
+        let visible = field_of_view(&map, HexCoord::from_axial(7, 0), 2, |&w| w);
+        assert!(visible.iter().all(|&c| map.bucket(c).is_some()));
+        assert_eq!(visible.len(), 9);
+    }
+
+    #[test]
+    fn wrapping_map_sees_each_cell_once() {
+        let mut map = StaticMap::init_with(HexWorldShape::WrappingHexagon(3, HexOrientation::Flat), || false);
+        let origin = HexCoord::from_axial(2, 0);
+        assert_eq!(field_of_view(&map, origin, 4, |&w| w).len(), 19);
+
+        // A wall just across the seam hides the cell behind it
+        let wall = map.index().neighbour(origin, Edge::R);
+        let behind = map.index().neighbour(wall, Edge::R);
+        assert_ne!(wall, origin.neighbour(Edge::R));
+        map.set(wall, Some(true));
+        let visible = field_of_view(&map, origin, 4, |&w| w);
+
+        assert!(visible.contains(&wall));
+        assert!(!visible.contains(&behind));
+        assert!(!has_line_of_sight(&map, origin, behind, |&w| w));
+        assert!(visible.iter().all(|&c| map.index().canonical(c) == c));
+    }
+
+    #[test_case(HexCoord::from_axial(0, 0), HexCoord::from_axial(4, -1))]
+    #[test_case(HexCoord::from_axial(-3, 1), HexCoord::from_axial(3, 2))]
+    #[test_case(HexCoord::from_axial(2, -5), HexCoord::from_axial(-2, 4))]
+    fn line_of_sight_is_symmetric(a: HexCoord, b: HexCoord) {
+        let walls: Vec<_> = HexCoord::from_axial(0, 0)
+            .spiral_iter(6)
+            .filter(|c| (c.q() * 7 + c.r() * 3).rem_euclid(5) == 0)
+            .collect();
+        let map = walled_map(&walls);
+        assert_eq!(
+            has_line_of_sight(&map, a, b, |&w| w),
+            has_line_of_sight(&map, b, a, |&w| w)
+        );
+
+        let from_a = field_of_view(&map, a, 10, |&w| w);
+        let from_b = field_of_view(&map, b, 10, |&w| w);
+        assert_eq!(from_a.contains(&b), from_b.contains(&a));
+    }
+}
@@ -1,14 +1,64 @@
-use crate::{HexCoord, HexCoordinate, HexOrientation, MapIndex};
+use crate::{FracHexCoord, HexCoord, HexCoordinate, HexOrientation, HexSide, HexVertex, MapIndex};
 
-#[derive(Copy, Clone)]
+// Masks of arbitrary cells aren't Copy, they are indexed by a CustomIndexer instead of a shape
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HexWorldShape {
     // Hexagon with it's radius in cells incl origin
     Hexagon(usize, HexOrientation),
+    // Hexagon with opposite sides joined so it has no edges
+    WrappingHexagon(usize, HexOrientation),
     // Rectangle with it's width and height in cells
     Rectangle(usize, usize, HexOrientation),
     // Rectangle with equal width and height
     Square(usize, HexOrientation),
+    // Rectangle joined along one axis into a cylinder
+    WrappingRectangle(usize, usize, WrapAxis, HexOrientation),
+    // Width along the first coordinate of the axis pair, height along the second
+    Parallelogram(usize, usize, ParallelogramAxis, HexOrientation),
+    // Cells along each side, the pointing decides the orientation
+    Triangle(usize, TrianglePointing),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WrapAxis {
+    // East and west edges meet
+    Horizontal,
+    // North and south edges meet
+    Vertical,
+}
+
+// The pair of cube coordinates running along a parallelogram's sides
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParallelogramAxis {
+    QR,
+    RS,
+    SQ,
+}
+
+// Triangle sides follow the axes, so flat hexagons make triangles pointing left or right and
+// pointy hexagons make triangles pointing up or down
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrianglePointing {
+    Up,
+    Down,
+    Left,
+    Right,
 }
+
+impl TrianglePointing {
+    #[inline]
+    pub fn orientation(self) -> HexOrientation {
+        match self {
+            TrianglePointing::Left | TrianglePointing::Right => HexOrientation::Flat,
+            TrianglePointing::Up | TrianglePointing::Down => HexOrientation::Pointy,
+        }
+    }
+}
+
 #[cfg(not(feature = "bevy"))]
 type Vec2 = glam::Vec2;
 #[cfg(feature = "bevy")]
@@ -39,8 +89,8 @@ where
     pub fn new(world_shape: HexWorldShape, cell_size: U) -> Self {
         Self {
             cell_size,
+            indexer: MapIndex::new(world_shape),
             world_shape,
-            indexer: MapIndex::new(world_shape)
         }
     }
 }
@@ -62,25 +112,69 @@ where
         Vec2::new(x, -y)
     }
 
+    pub fn world_to_coord(&self, position: Vec2) -> HexCoord {
+        let size: f32 = self.cell_size.into();
+        let orientation = self.world_shape.orientation();
+
+        let x = position.x / size;
+        let y = -position.y / size;
+
+        let q = orientation.b0() * x + orientation.b1() * y;
+        let r = orientation.b2() * x + orientation.b3() * y;
+
+        FracHexCoord::from_axial(q, r).round()
+    }
+
+    pub fn world_to_coord_checked(&self, position: Vec2) -> Option<HexCoord> {
+        let coord = self.world_to_coord(position);
+        self.indexer.contains(coord).then(|| self.indexer.canonical(coord))
+    }
+
+    pub fn vertex_to_world(&self, vertex: HexVertex) -> Vec2 {
+        let size: f32 = self.cell_size.into();
+        // corner_vec starts one corner behind HexVertex's numbering
+        let corner = self.world_shape.orientation().corner_vec((vertex.corner() + 5) % 6);
+        self.coord_to_world(vertex.cell()) + corner * size
+    }
+
+    // The end points of a side, in the same order as HexSide::vertices
+    pub fn side_to_world(&self, side: HexSide) -> (Vec2, Vec2) {
+        let [a, b] = side.vertices();
+        (self.vertex_to_world(a), self.vertex_to_world(b))
+    }
+
+    // The copy of `coord` closest to `near` on a wrapping world, for drawing cells across the seam
+    pub fn coord_to_world_near(&self, coord: HexCoord, near: HexCoord) -> Vec2 {
+        self.coord_to_world(self.indexer.nearest_image(near, coord))
+    }
+
     pub fn coord_to_world_v3(&self, coord: HexCoord) -> Vec3 {
         let v = self.coord_to_world(coord);
         Vec3::new(v.x, v.y, 0.0)
     }
 
     pub fn center(&self) -> HexCoord {
-        match self.world_shape {
-            HexWorldShape::Hexagon(_, _) => HexCoord::new(0,0,0),
-            HexWorldShape::Rectangle(_, _, _) | HexWorldShape::Square(_,_) => self.indexer.coord(self.indexer.capacity() / 2)
-        }
+        self.indexer.center()
     }
 }
 
 impl HexWorldShape {
+    // Every cell of the shape, in index order
+    pub fn coords(&self) -> impl Iterator<Item = HexCoord> {
+        let index = MapIndex::new(*self);
+        (0..index.capacity()).map(move |i| index.coord(i))
+    }
+
     pub fn orientation(&self) -> &HexOrientation {
         match self {
             HexWorldShape::Hexagon(_, o) |
+            HexWorldShape::WrappingHexagon(_, o) |
             HexWorldShape::Rectangle(_, _, o) |
-            HexWorldShape::Square(_, o) => o,
+            HexWorldShape::Square(_, o) |
+            HexWorldShape::WrappingRectangle(_, _, _, o) |
+            HexWorldShape::Parallelogram(_, _, _, o) => o,
+            HexWorldShape::Triangle(_, TrianglePointing::Left | TrianglePointing::Right) => &HexOrientation::Flat,
+            HexWorldShape::Triangle(_, TrianglePointing::Up | TrianglePointing::Down) => &HexOrientation::Pointy,
         }
     }
 }
@@ -90,10 +184,113 @@ mod tests {
     use super::*;
     use test_case::test_case;
 
+    const SQRT_3: f32 = 1.732_050_8;
+
+    fn is_copy<T: Copy>(_: T) {}
+
+    #[test]
+    fn shapes_indexers_and_worlds_are_copy() {
+        let shape = HexWorldShape::Triangle(3, TrianglePointing::Up);
+        let world = HexWorld::new(shape, 1.0);
+        is_copy(shape);
+        is_copy(world.indexer);
+        is_copy(world);
+        assert_eq!(world.indexer.capacity(), MapIndex::new(shape).capacity());
+    }
+
     #[test_case(HexWorldShape::Square(5, HexOrientation::Pointy), HexCoord::from_axial(1,2))]
     #[test_case(HexWorldShape::Square(5, HexOrientation::Flat), HexCoord::from_axial(2,1))]
+    #[test_case(HexWorldShape::Hexagon(4, HexOrientation::Pointy), HexCoord::from_axial(0,0))]
+    #[test_case(HexWorldShape::Parallelogram(5, 3, ParallelogramAxis::QR, HexOrientation::Flat), HexCoord::from_axial(2,1))]
+    #[test_case(HexWorldShape::Triangle(7, TrianglePointing::Right), HexCoord::from_axial(2,2))]
     fn world_center_tests(world_shape: HexWorldShape, expected: HexCoord) {
         let world = HexWorld::new(world_shape, 1.0);
         assert_eq!(world.center(), expected)
     }
+
+    #[test_case(HexOrientation::Flat, 1.0)]
+    #[test_case(HexOrientation::Pointy, 1.0)]
+    #[test_case(HexOrientation::Flat, 32.0)]
+    #[test_case(HexOrientation::Pointy, 0.25)]
+    fn world_to_coord_inverts_coord_to_world(orientation: HexOrientation, size: f32) {
+        let world = HexWorld::new(HexWorldShape::Hexagon(6, orientation), size);
+        for q in -5..=5 {
+            for r in -5..=5 {
+                let coord = HexCoord::from_axial(q, r);
+                assert_eq!(world.world_to_coord(world.coord_to_world(coord)), coord);
+            }
+        }
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn world_to_coord_points_inside_cell(orientation: HexOrientation) {
+        let world = HexWorld::new(HexWorldShape::Hexagon(4, orientation), 10.0);
+        let coord = HexCoord::from_axial(2, -1);
+        let center = world.coord_to_world(coord);
+        for i in 0..6 {
+            // Just inside each corner and each face
+            let corner = center + orientation.corner_vec(i) * 9.5;
+            let face = center + orientation.face_vec(i) * 8.5;
+            assert_eq!(world.world_to_coord(corner), coord);
+            assert_eq!(world.world_to_coord(face), coord);
+        }
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn vertex_to_world_is_shared_by_cells(orientation: HexOrientation) {
+        let world = HexWorld::new(HexWorldShape::Hexagon(4, orientation), 3.0);
+        let cell = HexCoord::from_axial(1, -2);
+        for i in 0..6 {
+            let vertex = HexVertex::new(cell, i);
+            let position = world.vertex_to_world(vertex);
+            for c in vertex.cells() {
+                let d = world.coord_to_world(c).distance(position);
+                assert!((d - 3.0).abs() < 1e-4, "Vertex {i} is {d} from {c}");
+            }
+        }
+    }
+
+    #[test_case(HexOrientation::Flat)]
+    #[test_case(HexOrientation::Pointy)]
+    fn side_to_world_lies_between_cells(orientation: HexOrientation) {
+        let world = HexWorld::new(HexWorldShape::Hexagon(4, orientation), 2.0);
+        for side in HexSide::of_cell(HexCoord::from_axial(-1, 2)) {
+            let (a, b) = world.side_to_world(side);
+            let [c0, c1] = side.cells().map(|c| world.coord_to_world(c));
+            assert!(((a + b) / 2.0).distance((c0 + c1) / 2.0) < 1e-4);
+            assert!((a.distance(b) - 2.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn coord_to_world_near_draws_across_seam() {
+        let world = HexWorld::new(HexWorldShape::WrappingRectangle(8, 4, WrapAxis::Horizontal, HexOrientation::Pointy), 1.0);
+        let left = world.indexer.offset_coord(0, 2);
+        let right = world.indexer.offset_coord(7, 2);
+
+        let near = world.coord_to_world_near(right, left);
+
+        assert!((near.distance(world.coord_to_world(left)) - SQRT_3).abs() < 1e-4);
+        assert_eq!(world.world_to_coord(near), world.indexer.nearest_image(left, right));
+        assert_eq!(world.coord_to_world_near(right, right), world.coord_to_world(right));
+    }
+
+    #[test_case(HexWorldShape::Hexagon(3, HexOrientation::Pointy), 19)]
+    #[test_case(HexWorldShape::Rectangle(4, 3, HexOrientation::Flat), 12)]
+    #[test_case(HexWorldShape::Triangle(4, TrianglePointing::Down), 10)]
+    fn shape_coords_are_all_contained(shape: HexWorldShape, count: usize) {
+        let index = MapIndex::new(shape);
+        let coords: Vec<_> = shape.coords().collect();
+        assert_eq!(coords.len(), count);
+        assert!(coords.iter().enumerate().all(|(i, c)| index.try_index(*c) == Some(i)));
+    }
+
+    #[test]
+    fn world_to_coord_checked_outside_world_is_none() {
+        let world = HexWorld::new(HexWorldShape::Hexagon(2, HexOrientation::Flat), 1.0);
+        assert_eq!(world.world_to_coord_checked(Vec2::new(0.1, -0.1)), Some(HexCoord::from_axial(0, 0)));
+        assert_eq!(world.world_to_coord_checked(Vec2::new(100.0, 100.0)), None);
+    }
 }